@@ -47,11 +47,7 @@ fn basic_checkout() -> Html {
                 let error = error.clone();
                 let paid = paid.clone();
                 wasm_bindgen_futures::spawn_local(async move {
-                    let params = ConfirmPaymentParams {
-                        return_url: None,
-                        save_payment_method: None,
-                        extra: None,
-                    };
+                    let params = ConfirmPaymentParams::default();
                     match confirm_payment(&s.into(), &e.into(), params, None, true).await {
                         PaymentResult::Success(_) => paid.set(true),
                         PaymentResult::Error(err) => error.set(Some(err.message)),
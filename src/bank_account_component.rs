@@ -0,0 +1,196 @@
+//! A Yew form for verifying a US bank account with microdeposits.
+//!
+//! When Stripe cannot verify an ACH account instantly, it sends one or two
+//! small deposits and leaves the PaymentIntent in `requires_action` with
+//! [`NextAction::VerifyWithMicrodeposits`](crate::client::NextAction::VerifyWithMicrodeposits).
+//! [`StripeMicrodepositForm`] asks the customer for the two amounts or the
+//! descriptor code and calls `stripe.verifyMicrodepositsForPayment`.
+
+use crate::client::{
+    verify_microdeposits_for_payment, MicrodepositVerification, PaymentIntent, StripeError,
+    StripeOptions, VerifyWithMicrodeposits,
+};
+use crate::next_action_component::format_timestamp;
+use crate::stripe_action::use_stripe_action;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+/// Properties for the [`StripeMicrodepositForm`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `stripe_options` – `locale`, `stripeAccount`, etc. for the Stripe instance.
+/// * `client_secret` – The PaymentIntent client secret.
+/// * `details` – The intent's `next_action.verify_with_microdeposits`; decides
+///   between amount and descriptor-code entry.
+/// * `on_success` – Callback invoked with the updated [`PaymentIntent`].
+/// * `on_error` – Callback invoked with [`StripeError`] on failure.
+/// * `button_label` – Override the button text (default: `"Verify"`).
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeMicrodepositFormProps {
+    pub publishable_key: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    pub client_secret: String,
+    #[prop_or_default]
+    pub details: VerifyWithMicrodeposits,
+    #[prop_or_default]
+    pub on_success: Callback<PaymentIntent>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub button_label: Option<String>,
+}
+
+/// Yew function component for microdeposit verification.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// if let Some(NextAction::VerifyWithMicrodeposits(details)) = intent.next_action.clone() {
+///     html! {
+///         <StripeMicrodepositForm
+///             publishable_key="pk_test_123".to_string()
+///             client_secret={client_secret.clone()}
+///             {details}
+///             on_success={on_verified}
+///         />
+///     }
+/// }
+/// ```
+#[function_component(StripeMicrodepositForm)]
+pub fn stripe_microdeposit_form(props: &StripeMicrodepositFormProps) -> Html {
+    let action = use_stripe_action(
+        props.publishable_key.clone(),
+        props.stripe_options.clone(),
+        props.on_error.clone(),
+    );
+    let first = use_state(String::new);
+    let second = use_state(String::new);
+    let code = use_state(String::new);
+    let descriptor = props.details.microdeposit_type.as_deref() == Some("descriptor_code");
+
+    let input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            state.set(e.target_unchecked_into::<HtmlInputElement>().value())
+        })
+    };
+
+    let on_click = {
+        let first = first.clone();
+        let second = second.clone();
+        let code = code.clone();
+        let action = action.clone();
+        let props = props.clone();
+        Callback::from(move |_: MouseEvent| {
+            let verification = if descriptor {
+                MicrodepositVerification::DescriptorCode(code.trim().to_uppercase())
+            } else {
+                match (cents(&first), cents(&second)) {
+                    (Some(a), Some(b)) => MicrodepositVerification::Amounts([a, b]),
+                    _ => return action.fail("Enter both deposit amounts, e.g. 0.32"),
+                }
+            };
+            let secret = props.client_secret.clone();
+            action.run(
+                move |client| async move {
+                    verify_microdeposits_for_payment(client.js(), &secret, &verification).await
+                },
+                {
+                    let on_success = props.on_success.clone();
+                    move |intent| on_success.emit(intent)
+                },
+            );
+        })
+    };
+
+    let field = "rounded border border-slate-300 px-3 py-2 w-full";
+    html! {
+        <div class="flex flex-col gap-3 w-full">
+            {
+                match props.details.arrival_date {
+                    Some(ts) => html! {
+                        <p class="text-sm text-slate-600">
+                            { format!("Deposits should arrive by {}.", format_timestamp(ts)) }
+                        </p>
+                    },
+                    None => Html::default(),
+                }
+            }
+            if descriptor {
+                <label class="flex flex-col gap-1 text-sm">
+                    { "6-character code from your bank statement (starts with SM)" }
+                    <input class={field} maxlength="6" autocomplete="off"
+                        value={(*code).clone()} oninput={input(&code)} />
+                </label>
+            } else {
+                <label class="flex flex-col gap-1 text-sm">
+                    { "First deposit amount" }
+                    <input class={field} inputmode="decimal" placeholder="0.32"
+                        value={(*first).clone()} oninput={input(&first)} />
+                </label>
+                <label class="flex flex-col gap-1 text-sm">
+                    { "Second deposit amount" }
+                    <input class={field} inputmode="decimal" placeholder="0.45"
+                        value={(*second).clone()} oninput={input(&second)} />
+                </label>
+            }
+            { action.button(
+                on_click,
+                props.button_label.clone().unwrap_or_else(|| "Verify".to_string()),
+                "Verifying…",
+            ) }
+            { action.error() }
+        </div>
+    }
+}
+
+/// Parse a deposit typed as dollars (`"0.32"`) or cents (`"32"`) into cents.
+fn cents(input: &str) -> Option<u32> {
+    let input = input.trim().trim_start_matches('$');
+    match input.split_once('.') {
+        Some((dollars, fraction)) if fraction.len() <= 2 => {
+            let dollars: u32 = if dollars.is_empty() {
+                0
+            } else {
+                dollars.parse().ok()?
+            };
+            let fraction: u32 = format!("{:0<2}", fraction).parse().ok()?;
+            dollars.checked_mul(100)?.checked_add(fraction)
+        }
+        Some(_) => None,
+        None => input.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cents;
+
+    #[test]
+    fn cents_parses_dollars_and_cents() {
+        assert_eq!(cents("0.32"), Some(32));
+        assert_eq!(cents(".5"), Some(50));
+        assert_eq!(cents("1."), Some(100));
+        assert_eq!(cents(" $0.45 "), Some(45));
+        assert_eq!(cents("32"), Some(32));
+    }
+
+    #[test]
+    fn cents_rejects_malformed_input() {
+        assert_eq!(cents("$1.234"), None);
+        assert_eq!(cents(""), None);
+        assert_eq!(cents("abc"), None);
+        assert_eq!(cents("0.3x"), None);
+    }
+
+    #[test]
+    fn cents_rejects_overflow() {
+        assert_eq!(cents("42949672.95"), Some(u32::MAX));
+        assert_eq!(cents("42949672.96"), None);
+        assert_eq!(cents("42949673.00"), None);
+        assert_eq!(cents("4294967296"), None);
+    }
+}
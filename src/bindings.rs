@@ -13,6 +13,9 @@
 //! - [`JsStripe`]: the primary Stripe client instance.
 //! - [`JsElements`]: factory for Stripe Elements.
 //! - [`JsPaymentElement`]: the Payment Element UI component.
+//! - [`JsEmbeddedCheckout`]: an embedded Stripe Checkout form.
+//! - [`JsCheckout`]: a Custom Checkout instance driving your own UI.
+//! - [`JsStripeConnect`]: a Connect.js instance for Connect embedded components.
 //!
 //! # Conventions
 //!
@@ -28,7 +31,8 @@
 //! in `client.rs`, which handles JSON conversion, error mapping, SCA/3DS and Yew async patterns.
 
 use wasm_bindgen::prelude::*;
-use web_sys::js_sys::Promise;
+use web_sys::js_sys::{Function, Promise};
+use web_sys::Element;
 
 #[wasm_bindgen]
 extern "C" {
@@ -83,6 +87,56 @@ extern "C" {
     #[derive(Debug, Clone)]
     pub type JsPaymentElement;
 
+    /// An Embedded Checkout instance for a Stripe Checkout Session.
+    ///
+    /// Created via `stripe.initEmbeddedCheckout(options)`; only one may exist
+    /// per page at a time, so call `destroy()` before creating another.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let checkout: JsEmbeddedCheckout = JsFuture::from(stripe.init_embedded_checkout(opts)?)
+    ///     .await?
+    ///     .unchecked_into();
+    /// checkout.mount("#checkout")?;
+    /// ```
+    #[wasm_bindgen(js_name = EmbeddedCheckout)]
+    #[derive(Debug, Clone)]
+    pub type JsEmbeddedCheckout;
+
+    /// A Custom Checkout instance: your own UI on top of a Checkout Session.
+    ///
+    /// Created via `stripe.initCheckout(options)`. Exposes the session state
+    /// and actions such as `applyPromotionCode` and `confirm`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let checkout: JsCheckout = JsFuture::from(Promise::resolve(&stripe.init_checkout(opts)?))
+    ///     .await?
+    ///     .unchecked_into();
+    /// let session = checkout.session()?;
+    /// ```
+    #[wasm_bindgen(js_name = Checkout)]
+    #[derive(Debug, Clone)]
+    pub type JsCheckout;
+
+    /// A Connect.js instance for rendering Connect embedded components.
+    ///
+    /// Created via `StripeConnect.init(params)` once Connect.js has loaded
+    /// (see [`use_connectjs`](crate::use_connectjs)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let connect = load_connect_and_initialize(params)?;
+    /// let payments: web_sys::Element = connect.create("payments")?;
+    /// container.append_child(&payments)?;
+    /// ```
+    #[wasm_bindgen(js_name = StripeConnectInstance)]
+    #[derive(Debug, Clone)]
+    pub type JsStripeConnect;
+
     //------------------------------------------------------------------------------
     // Constructors
     //------------------------------------------------------------------------------
@@ -107,6 +161,33 @@ extern "C" {
     #[wasm_bindgen(js_name = Stripe, js_namespace = window)]
     pub fn new_stripe(publishable_key: &str) -> JsStripe;
 
+    /// Create a new Stripe.js client with initialization options.
+    ///
+    /// Wraps the JS global `Stripe(publishableKey, options)` constructor.
+    ///
+    /// # Arguments
+    ///
+    /// - `publishable_key`: Your Stripe publishable key (starts with `pk_`).
+    /// - `options`: a JSON object with any of `locale`, `apiVersion`,
+    ///   `stripeAccount` and `betas`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JsStripe)`: the configured client.
+    /// - `Err(JsValue)`: a JS exception (invalid key or options).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let opts = serde_wasm_bindgen::to_value(&StripeOptions { locale: Some("fr".into()), ..Default::default() }).unwrap();
+    /// let stripe = new_stripe_with_options("pk_live_...", opts).unwrap();
+    /// ```
+    #[wasm_bindgen(catch, js_name = Stripe, js_namespace = window)]
+    pub fn new_stripe_with_options(
+        publishable_key: &str,
+        options: JsValue,
+    ) -> Result<JsStripe, JsValue>;
+
     //------------------------------------------------------------------------------
     // Instance Methods
     //------------------------------------------------------------------------------
@@ -209,6 +290,67 @@ extern "C" {
     #[wasm_bindgen(method, catch, js_name = unmount)]
     pub fn unmount(this: &JsPaymentElement) -> Result<(), JsValue>;
 
+    /// Mount a Stripe Element into a DOM element.
+    ///
+    /// Calls `element.mount(domElement)` in JS; use this with a Yew `NodeRef`
+    /// instead of a selector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let el = node_ref.cast::<web_sys::Element>().unwrap();
+    /// payment_el.mount_element(&el).unwrap();
+    /// ```
+    #[wasm_bindgen(method, catch, js_name = mount)]
+    pub fn mount_element(this: &JsPaymentElement, element: &Element) -> Result<(), JsValue>;
+
+    /// Update a mounted Stripe Element's options in place.
+    ///
+    /// Calls `element.update(options)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the element handle.
+    /// - `options`: the options to change, e.g. `{ amount: 4999 }`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// messaging_el.update(serde_wasm_bindgen::to_value(&opts)?).unwrap();
+    /// ```
+    #[wasm_bindgen(method, catch, js_name = update)]
+    pub fn update(this: &JsPaymentElement, options: JsValue) -> Result<(), JsValue>;
+
+    /// Remove a Stripe Element from the DOM and free its resources.
+    ///
+    /// Calls `element.destroy()` in JS. A destroyed element cannot be mounted again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// payment_el.destroy().unwrap();
+    /// ```
+    #[wasm_bindgen(method, catch, js_name = destroy)]
+    pub fn destroy(this: &JsPaymentElement) -> Result<(), JsValue>;
+
+    /// Clear the values of a mounted Stripe Element.
+    ///
+    /// Calls `element.clear()` in JS.
+    #[wasm_bindgen(method, catch, js_name = clear)]
+    pub fn clear(this: &JsPaymentElement) -> Result<(), JsValue>;
+
+    /// Subscribe to a Stripe Element event, e.g. `"change"` or `"ready"`.
+    ///
+    /// Calls `element.on(event, handler)` in JS.
+    #[wasm_bindgen(method, js_name = on)]
+    pub fn on(this: &JsPaymentElement, event: &str, handler: &Function);
+
+    /// Remove a handler added with [`on`](JsPaymentElement::on).
+    ///
+    /// Calls `element.off(event, handler)` in JS.
+    #[wasm_bindgen(method, js_name = off)]
+    pub fn off(this: &JsPaymentElement, event: &str, handler: &Function);
+
     /// Trigger validation on all Elements fields.
     ///
     /// Corresponds to `elements.submit()` in JS, returning a Promise
@@ -256,6 +398,31 @@ extern "C" {
     #[wasm_bindgen(method, catch, js_name = handleCardAction)]
     pub fn handle_card_action(this: &JsStripe, client_secret: &str) -> Result<Promise, JsValue>;
 
+    /// Complete whatever action an intent confirmed on your server requires.
+    ///
+    /// Calls `stripe.handleNextAction({ clientSecret })` in JS: 3DS, redirects,
+    /// vouchers or QR codes, for both PaymentIntents and SetupIntents.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `options`: a JSON object with `clientSecret`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ paymentIntent }`, `{ setupIntent }` or `{ error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let opts = serde_wasm_bindgen::to_value(&serde_json::json!({ "clientSecret": cs })).unwrap();
+    /// let promise = stripe.handle_next_action(opts).unwrap();
+    /// let result = wasm_bindgen_futures::JsFuture::from(promise).await?;
+    /// ```
+    #[wasm_bindgen(method, catch, js_name = handleNextAction)]
+    pub fn handle_next_action(this: &JsStripe, options: JsValue) -> Result<Promise, JsValue>;
+
     /// Confirm a PaymentIntent with provided options.
     ///
     /// Calls `stripe.confirmPayment(opts)` in JS, which may redirect or
@@ -285,4 +452,388 @@ extern "C" {
     /// ```
     #[wasm_bindgen(method, catch, js_name = confirmPayment)]
     pub fn confirm_payment(this: &JsStripe, options: JsValue) -> Result<Promise, JsValue>;
+
+    /// Retrieve a PaymentIntent using its client secret.
+    ///
+    /// Calls `stripe.retrievePaymentIntent(clientSecret)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `client_secret`: the PaymentIntent client secret string.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ paymentIntent }` or `{ error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let promise = stripe.retrieve_payment_intent(&client_secret).unwrap();
+    /// let result = wasm_bindgen_futures::JsFuture::from(promise).await?;
+    /// ```
+    #[wasm_bindgen(method, catch, js_name = retrievePaymentIntent)]
+    pub fn retrieve_payment_intent(
+        this: &JsStripe,
+        client_secret: &str,
+    ) -> Result<Promise, JsValue>;
+
+    /// Retrieve a SetupIntent using its client secret.
+    ///
+    /// Calls `stripe.retrieveSetupIntent(clientSecret)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `client_secret`: the SetupIntent client secret string.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ setupIntent }` or `{ error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let promise = stripe.retrieve_setup_intent(&client_secret).unwrap();
+    /// let result = wasm_bindgen_futures::JsFuture::from(promise).await?;
+    /// ```
+    #[wasm_bindgen(method, catch, js_name = retrieveSetupIntent)]
+    pub fn retrieve_setup_intent(this: &JsStripe, client_secret: &str) -> Result<Promise, JsValue>;
+
+    /// Collect US bank account details for a PaymentIntent (ACH Direct Debit).
+    ///
+    /// Calls `stripe.collectBankAccountForPayment(options)` in JS, which opens
+    /// the Financial Connections modal to link or enter an account.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `options`: a JSON object with `clientSecret` and
+    ///   `params: { payment_method_type, payment_method_data }`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ paymentIntent }` or `{ error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = collectBankAccountForPayment)]
+    pub fn collect_bank_account_for_payment(
+        this: &JsStripe,
+        options: JsValue,
+    ) -> Result<Promise, JsValue>;
+
+    /// Collect US bank account details for a SetupIntent.
+    ///
+    /// Calls `stripe.collectBankAccountForSetup(options)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `options`: same shape as [`collect_bank_account_for_payment`](JsStripe::collect_bank_account_for_payment).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ setupIntent }` or `{ error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = collectBankAccountForSetup)]
+    pub fn collect_bank_account_for_setup(
+        this: &JsStripe,
+        options: JsValue,
+    ) -> Result<Promise, JsValue>;
+
+    /// Confirm a PaymentIntent whose US bank account was just collected.
+    ///
+    /// Calls `stripe.confirmUsBankAccountPayment(clientSecret)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `client_secret`: the PaymentIntent client secret string.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ paymentIntent }` or `{ error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = confirmUsBankAccountPayment)]
+    pub fn confirm_us_bank_account_payment(
+        this: &JsStripe,
+        client_secret: &str,
+    ) -> Result<Promise, JsValue>;
+
+    /// Verify a bank account with the microdeposits Stripe sent to it.
+    ///
+    /// Calls `stripe.verifyMicrodepositsForPayment(clientSecret, data)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `client_secret`: the PaymentIntent client secret string.
+    /// - `data`: `{ amounts: [32, 45] }` or `{ descriptor_code: "SM11AA" }`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ paymentIntent }` or `{ error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = verifyMicrodepositsForPayment)]
+    pub fn verify_microdeposits_for_payment(
+        this: &JsStripe,
+        client_secret: &str,
+        data: JsValue,
+    ) -> Result<Promise, JsValue>;
+
+    /// Launch the Financial Connections modal to link bank accounts.
+    ///
+    /// Calls `stripe.collectFinancialConnectionsAccounts(options)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `options`: a JSON object `{ clientSecret }` with the Financial
+    ///   Connections Session client secret.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ financialConnectionsSession }` or `{ error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = collectFinancialConnectionsAccounts)]
+    pub fn collect_financial_connections_accounts(
+        this: &JsStripe,
+        options: JsValue,
+    ) -> Result<Promise, JsValue>;
+
+    /// Open the Stripe Identity verification modal.
+    ///
+    /// Calls `stripe.verifyIdentity(clientSecret)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `client_secret`: the VerificationSession client secret string.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{}` once submitted, or `{ error }`
+    ///   (code `session_cancelled` if the user closed the modal).
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = verifyIdentity)]
+    pub fn verify_identity(this: &JsStripe, client_secret: &str) -> Result<Promise, JsValue>;
+
+    /// Create an Embedded Checkout instance for a Checkout Session.
+    ///
+    /// Calls `stripe.initEmbeddedCheckout(options)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `options`: a JS object with `clientSecret` (string) or
+    ///   `fetchClientSecret` (function returning a Promise of a string), and
+    ///   optionally `onComplete` (function).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with a [`JsEmbeddedCheckout`].
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = initEmbeddedCheckout)]
+    pub fn init_embedded_checkout(this: &JsStripe, options: JsValue) -> Result<Promise, JsValue>;
+
+    /// Attach Embedded Checkout to the DOM.
+    ///
+    /// Calls `checkout.mount(selector)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsEmbeddedCheckout`.
+    /// - `selector`: a CSS selector, e.g. `"#checkout"`.
+    #[wasm_bindgen(method, catch, js_name = mount)]
+    pub fn mount(this: &JsEmbeddedCheckout, selector: &str) -> Result<(), JsValue>;
+
+    /// Attach Embedded Checkout to a DOM element.
+    ///
+    /// Calls `checkout.mount(element)` in JS.
+    #[wasm_bindgen(method, catch, js_name = mount)]
+    pub fn mount_element(this: &JsEmbeddedCheckout, element: &Element) -> Result<(), JsValue>;
+
+    /// Detach Embedded Checkout from the DOM; it can be mounted again.
+    ///
+    /// Calls `checkout.unmount()` in JS.
+    #[wasm_bindgen(method, catch, js_name = unmount)]
+    pub fn unmount(this: &JsEmbeddedCheckout) -> Result<(), JsValue>;
+
+    /// Remove Embedded Checkout from the page entirely.
+    ///
+    /// Calls `checkout.destroy()` in JS. Required before creating another instance.
+    #[wasm_bindgen(method, catch, js_name = destroy)]
+    pub fn destroy(this: &JsEmbeddedCheckout) -> Result<(), JsValue>;
+
+    /// Create a Custom Checkout instance for a Checkout Session.
+    ///
+    /// Calls `stripe.initCheckout(options)` in JS. Depending on the Stripe.js
+    /// version this returns the instance or a Promise of it; wrap the result
+    /// in `Promise::resolve` to handle both.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `options`: a JS object with `clientSecret` or `fetchClientSecret`,
+    ///   and optionally `elementsOptions` (`appearance`, `loader`).
+    #[wasm_bindgen(method, catch, js_name = initCheckout)]
+    pub fn init_checkout(this: &JsStripe, options: JsValue) -> Result<JsValue, JsValue>;
+
+    /// Current Checkout Session state.
+    ///
+    /// Calls `checkout.session()` in JS.
+    #[wasm_bindgen(method, catch, js_name = session)]
+    pub fn session(this: &JsCheckout) -> Result<JsValue, JsValue>;
+
+    /// Subscribe to a Custom Checkout event, e.g. `"change"`.
+    ///
+    /// Calls `checkout.on(event, handler)` in JS; `handler` receives the
+    /// updated session.
+    #[wasm_bindgen(method, js_name = on)]
+    pub fn on(this: &JsCheckout, event: &str, handler: &Function);
+
+    /// Remove a handler added with [`on`](JsCheckout::on).
+    ///
+    /// Calls `checkout.off(event, handler)` in JS; errors if this Stripe.js
+    /// version has no `off`.
+    #[wasm_bindgen(method, catch, js_name = off)]
+    pub fn off(this: &JsCheckout, event: &str, handler: &Function) -> Result<(), JsValue>;
+
+    /// Tear down the Custom Checkout instance.
+    ///
+    /// Calls `checkout.destroy()` in JS; errors if this Stripe.js version has
+    /// no `destroy`.
+    #[wasm_bindgen(method, catch, js_name = destroy)]
+    pub fn destroy(this: &JsCheckout) -> Result<(), JsValue>;
+
+    /// Apply a promotion code to the session.
+    ///
+    /// Calls `checkout.applyPromotionCode(code)` in JS.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ type: "success", session }` or `{ type: "error", error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = applyPromotionCode)]
+    pub fn apply_promotion_code(this: &JsCheckout, code: &str) -> Result<Promise, JsValue>;
+
+    /// Remove the applied promotion code.
+    ///
+    /// Calls `checkout.removePromotionCode()` in JS.
+    #[wasm_bindgen(method, catch, js_name = removePromotionCode)]
+    pub fn remove_promotion_code(this: &JsCheckout) -> Result<Promise, JsValue>;
+
+    /// Update the shipping address, recalculating tax and shipping.
+    ///
+    /// Calls `checkout.updateShippingAddress(address)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsCheckout`.
+    /// - `address`: `{ name, address: { line1, city, postal_code, country, … } }`.
+    #[wasm_bindgen(method, catch, js_name = updateShippingAddress)]
+    pub fn update_shipping_address(this: &JsCheckout, address: JsValue)
+        -> Result<Promise, JsValue>;
+
+    /// Change the quantity of one line item.
+    ///
+    /// Calls `checkout.updateLineItemQuantity({ lineItem, quantity })` in JS.
+    #[wasm_bindgen(method, catch, js_name = updateLineItemQuantity)]
+    pub fn update_line_item_quantity(
+        this: &JsCheckout,
+        options: JsValue,
+    ) -> Result<Promise, JsValue>;
+
+    /// Confirm the Checkout Session and collect payment.
+    ///
+    /// Calls `checkout.confirm(options)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsCheckout`.
+    /// - `options`: `{ returnUrl, redirect, email, billingAddress, shippingAddress, … }`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ type: "success", session }` or `{ type: "error", error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = confirm)]
+    pub fn confirm(this: &JsCheckout, options: JsValue) -> Result<Promise, JsValue>;
+
+    /// Create a Payment Element bound to the Checkout Session.
+    ///
+    /// Calls `checkout.createPaymentElement(options)` in JS.
+    #[wasm_bindgen(method, catch, js_name = createPaymentElement)]
+    pub fn create_payment_element(
+        this: &JsCheckout,
+        options: JsValue,
+    ) -> Result<JsPaymentElement, JsValue>;
+
+    /// Initialize Connect.js for a connected account.
+    ///
+    /// Calls `StripeConnect.init(params)` in JS — what `loadConnectAndInitialize`
+    /// does once the Connect.js script has loaded.
+    ///
+    /// # Arguments
+    ///
+    /// - `params`: a JS object with `publishableKey`, `fetchClientSecret`
+    ///   (function returning a Promise of an Account Session client secret),
+    ///   and optionally `appearance` and `locale`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JsStripeConnect)`: the Connect instance.
+    /// - `Err(JsValue)`: JS exception (Connect.js not loaded, invalid params).
+    #[wasm_bindgen(catch, js_name = init, js_namespace = StripeConnect)]
+    pub fn load_connect_and_initialize(params: JsValue) -> Result<JsStripeConnect, JsValue>;
+
+    /// Create a Connect embedded component element.
+    ///
+    /// Calls `stripeConnectInstance.create(tagName)` in JS.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripeConnect` instance.
+    /// - `tag_name`: e.g. `"account-onboarding"`, `"payments"`, `"payouts"`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Element)`: the unattached `<stripe-connect-…>` element.
+    /// - `Err(JsValue)`: JS exception for an unknown component.
+    #[wasm_bindgen(method, catch, js_name = create)]
+    pub fn create(this: &JsStripeConnect, tag_name: &str) -> Result<Element, JsValue>;
+
+    /// Update appearance or locale of every component from this instance.
+    ///
+    /// Calls `stripeConnectInstance.update(options)` in JS.
+    #[wasm_bindgen(method, catch, js_name = update)]
+    pub fn update(this: &JsStripeConnect, options: JsValue) -> Result<(), JsValue>;
+
+    /// End the Account Session.
+    ///
+    /// Calls `stripeConnectInstance.logout()` in JS.
+    #[wasm_bindgen(method, catch, js_name = logout)]
+    pub fn logout(this: &JsStripeConnect) -> Result<Promise, JsValue>;
+
+    /// Create a nonce for an Issuing card ephemeral key.
+    ///
+    /// Calls `stripe.createEphemeralKeyNonce(options)` in JS. Send the nonce
+    /// to your server, which creates the ephemeral key the Issuing display
+    /// elements need.
+    ///
+    /// # Arguments
+    ///
+    /// - `this`: the `JsStripe` instance.
+    /// - `options`: `{ issuingCard: "ic_…" }`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Promise)`: resolves with `{ nonce }` or `{ error }`.
+    /// - `Err(JsValue)`: JS exception on immediate error.
+    #[wasm_bindgen(method, catch, js_name = createEphemeralKeyNonce)]
+    pub fn create_ephemeral_key_nonce(
+        this: &JsStripe,
+        options: JsValue,
+    ) -> Result<Promise, JsValue>;
 }
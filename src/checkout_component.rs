@@ -3,25 +3,30 @@
 //! This component handles loading Stripe.js, mounting the Payment Element,
 //! orchestrating form validation, SCA/3DS challenges, and payment confirmation,
 //! then emits typed success or error callbacks to your app..
+//!
+//! [`StripeCheckout`] ships Tailwind-styled markup; [`HeadlessStripeCheckout`]
+//! runs the same flow and hands its state to your own `render` callback.
 
 use crate::{
     client::{
-        confirm_payment, mount_payment_element, ConfirmPaymentParams, ElementsOptions,
-        PaymentElementOptions, PaymentResult, StripeError,
+        confirm_payment, on_element_change, retrieve_payment_intent, update_payment_element,
+        wait_for_terminal_status, ConfirmPaymentParams, ElementChangeListener, ElementsOptions,
+        NextAction, PaymentElementOptions, PaymentIntent, PaymentIntentStatus, PaymentResult,
+        PollOptions, StripeClient, StripeError, StripeOptions,
     },
-    JsElements, JsPaymentElement, JsStripe,
+    JsElements, JsPaymentElement, JsStripe, StripeNextAction,
 };
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use web_sys::Element;
+use yew::functional::hook;
 use yew::prelude::*;
 
-// Needed for working with JsValue and conversions (trait imports).
-use gloo_utils::format::JsValueSerdeExt;
-use wasm_bindgen::JsCast;
-use wasm_bindgen::JsValue;
-use web_sys::js_sys;
-
 use crate::client::validate_payment_element;
 
-use crate::use_stripejs;
+use crate::{use_stripejs_status, StripeJsStatus};
 
 /// Data emitted when a payment completes successfully.
 ///
@@ -45,9 +50,20 @@ pub struct StripeCheckoutSuccess {
 ///
 /// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
 /// * `client_secret` – The PaymentIntent client secret from your backend.
+/// * `stripe_options` – `locale`, `apiVersion`, `stripeAccount` or `betas` for the Stripe instance.
 /// * `payment_element_options` – Customize the Payment Element layout.
+/// * `confirm_params` – Extra `confirmParams` (billing details, shipping, receipt email…).
 /// * `on_success` – Callback invoked with [`StripeCheckoutSuccess`] on success.
 /// * `on_error` – Callback invoked with [`StripeError`] on failure.
+/// * `on_processing` – Callback invoked with the [`PaymentIntent`] when the payment is
+///   still `processing` (ACH, SEPA, some wallets) after confirmation.
+/// * `on_next_action` – Callback invoked when the customer must finish paying
+///   elsewhere (OXXO/Boleto/Konbini voucher, PayNow/PromptPay/WeChat Pay QR code).
+///   The voucher or QR code is also rendered below the form.
+/// * `poll_processing` – When set, keep polling a `processing` payment and emit
+///   `on_success`/`on_error` once it settles. Otherwise the pending state is final.
+/// * `handle` – A [`StripeCheckoutHandle`] from [`use_stripe_checkout`] letting the
+///   parent submit, validate or reset the form.
 /// * `button_label` – Override the Pay button text (default: `"Pay Now"`).
 /// * `children` – Extra Yew nodes (e.g. product summary) rendered above the form.
 #[derive(Properties, PartialEq, Clone)]
@@ -55,12 +71,24 @@ pub struct StripeCheckoutProps {
     pub publishable_key: String,
     pub client_secret: String,
     #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    #[prop_or_default]
     pub payment_element_options: Option<PaymentElementOptions>,
     #[prop_or_default]
+    pub confirm_params: Option<ConfirmPaymentParams>,
+    #[prop_or_default]
     pub on_success: Callback<StripeCheckoutSuccess>,
     #[prop_or_default]
     pub on_error: Callback<StripeError>,
     #[prop_or_default]
+    pub on_processing: Callback<PaymentIntent>,
+    #[prop_or_default]
+    pub on_next_action: Callback<NextAction>,
+    #[prop_or_default]
+    pub poll_processing: Option<PollOptions>,
+    #[prop_or_default]
+    pub handle: Option<StripeCheckoutHandle>,
+    #[prop_or_default]
     pub button_label: Option<String>,
     #[prop_or_default]
     pub children: Children, // allow extra UI (product summary etc)
@@ -70,7 +98,7 @@ pub struct StripeCheckoutProps {
 ///
 /// This component will:
 /// 1. Dynamically load `https://js.stripe.com/v3/` once per page.
-/// 2. Instantiate Stripe and mount a Payment Element into `#stripe-payment-element`.
+/// 2. Instantiate Stripe and mount a Payment Element into its own container.
 /// 3. Handle form submission:
 ///    - Validate card details (`elements.submit()`).
 ///    - Call `stripe.confirmPayment()` with SCA/3DS support.
@@ -80,6 +108,9 @@ pub struct StripeCheckoutProps {
 /// Designed for global-scale deployments: all calls are async, non-blocking,
 /// and safe to rerender across concurrent Yew renders.
 ///
+/// It is a Tailwind-styled [`HeadlessStripeCheckout`]; use that directly to
+/// bring your own markup.
+///
 /// # Example
 ///
 /// ```rust
@@ -113,7 +144,7 @@ pub struct StripeCheckoutProps {
 ///
 /// - All network calls are performed via `wasm-bindgen-futures::spawn_local`.
 /// - Component re-renders are minimal; internal state is managed via `use_state`.
-/// - Supports concurrent mounts on the same page by unique CSS selectors.
+/// - Each instance mounts into its own element, so several checkouts can share a page.
 ///
 /// # Errors
 ///
@@ -125,33 +156,309 @@ pub struct StripeCheckoutProps {
 /// [`StripeCheckoutSuccess`]: StripeCheckoutSuccess
 #[function_component(StripeCheckout)]
 pub fn stripe_checkout(props: &StripeCheckoutProps) -> Html {
-    let stripe_ready = use_stripejs();
-    let state = use_state(|| None::<(JsStripe, JsElements, JsPaymentElement)>);
-    let error = use_state(|| None::<String>);
+    let render = {
+        let button_label = props.button_label.clone();
+        let children = props.children.clone();
+        Callback::from(move |state: StripeCheckoutState| {
+            html! {
+                <div class="flex flex-col gap-4 items-center w-full">
+                    { for children.iter() }
+                    <div class="w-full mb-2">{ state.element.clone() }</div>
+                    <button
+                        type="button"
+                        onclick={state.submit.reform(|_: MouseEvent| ())}
+                        disabled={!state.ready || state.loading || state.processing}
+                        class="rounded bg-blue-600 text-white font-semibold px-5 py-2 shadow hover:bg-blue-700 transition disabled:opacity-50">
+                        {
+                            if state.loading {
+                                "Processing…".to_string()
+                            } else {
+                                button_label.clone().unwrap_or_else(|| "Pay Now".to_string())
+                            }
+                        }
+                    </button>
+                    {
+                        if let StripeJsStatus::Failed(reason) = &state.stripe_js {
+                            html!{
+                                <div class="text-red-500 text-sm" role="alert">
+                                    { "Payments are unavailable: " }{ reason }
+                                    <button
+                                        type="button"
+                                        onclick={state.retry_load.reform(|_: MouseEvent| ())}
+                                        class="ml-2 underline">
+                                        { "Try again" }
+                                    </button>
+                                </div>
+                            }
+                        } else if let Some(err) = &state.error {
+                            html!{ <div class="text-red-500 text-sm">{ &err.message }</div> }
+                        } else if let Some(action) = &state.next_action {
+                            html!{ <StripeNextAction next_action={action.clone()} /> }
+                        } else if state.processing {
+                            html!{
+                                <div class="text-slate-600 text-sm" role="status">
+                                    { "Your payment is processing. We'll confirm once it completes." }
+                                </div>
+                            }
+                        } else {
+                            Html::default()
+                        }
+                    }
+                </div>
+            }
+        })
+    };
+
+    html! {
+        <HeadlessStripeCheckout
+            publishable_key={props.publishable_key.clone()}
+            client_secret={props.client_secret.clone()}
+            stripe_options={props.stripe_options.clone()}
+            payment_element_options={props.payment_element_options.clone()}
+            confirm_params={props.confirm_params.clone()}
+            on_success={props.on_success.clone()}
+            on_error={props.on_error.clone()}
+            on_processing={props.on_processing.clone()}
+            on_next_action={props.on_next_action.clone()}
+            poll_processing={props.poll_processing.clone()}
+            handle={props.handle.clone()}
+            {render}
+        />
+    }
+}
+
+/// Everything a [`HeadlessStripeCheckout`] render callback needs to draw a
+/// checkout form.
+///
+/// # Fields
+///
+/// * `ready` – Stripe.js has loaded and the Payment Element is mounted.
+/// * `complete` – The customer has filled in every required field.
+/// * `loading` – A submission is validating or confirming.
+/// * `processing` – The payment was confirmed but is still `processing`.
+/// * `stripe_js` – Whether Stripe.js is loading, ready or failed to load.
+/// * `error` – The last validation or payment error, or why Stripe.js failed to load.
+/// * `next_action` – Voucher or QR code the customer must act on.
+/// * `result` – Set once the payment succeeded.
+/// * `submit` – Validate and confirm the payment; ignored until `ready` or while `loading`.
+/// * `retry_load` – Load Stripe.js again after it failed.
+/// * `element` – The Payment Element's container. Render it exactly once,
+///   on every render, wherever the form should appear; without it nothing
+///   mounts and `error` says so.
+#[derive(Clone, PartialEq)]
+pub struct StripeCheckoutState {
+    pub ready: bool,
+    pub complete: bool,
+    pub loading: bool,
+    pub processing: bool,
+    pub stripe_js: StripeJsStatus,
+    pub error: Option<StripeError>,
+    pub next_action: Option<NextAction>,
+    pub result: Option<StripeCheckoutSuccess>,
+    pub submit: Callback<()>,
+    pub retry_load: Callback<()>,
+    pub element: Html,
+}
+
+/// Properties for the [`HeadlessStripeCheckout`] component.
+///
+/// The same as [`StripeCheckoutProps`], minus the styled button and children:
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `client_secret` – The PaymentIntent client secret from your backend.
+/// * `stripe_options` – `locale`, `apiVersion`, `stripeAccount` or `betas` for the Stripe instance.
+/// * `payment_element_options` – Customize the Payment Element layout.
+/// * `confirm_params` – Extra `confirmParams` (billing details, shipping, receipt email…).
+/// * `on_success` – Callback invoked with [`StripeCheckoutSuccess`] on success.
+/// * `on_error` – Callback invoked with [`StripeError`] on failure.
+/// * `on_processing` – Callback invoked with the [`PaymentIntent`] while it is `processing`.
+/// * `on_next_action` – Callback invoked when the customer must finish paying elsewhere.
+/// * `poll_processing` – Keep polling a `processing` payment until it settles.
+/// * `handle` – A [`StripeCheckoutHandle`] from [`use_stripe_checkout`].
+/// * `render` – Draws the form from a [`StripeCheckoutState`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct HeadlessStripeCheckoutProps {
+    pub publishable_key: String,
+    pub client_secret: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    #[prop_or_default]
+    pub payment_element_options: Option<PaymentElementOptions>,
+    #[prop_or_default]
+    pub confirm_params: Option<ConfirmPaymentParams>,
+    #[prop_or_default]
+    pub on_success: Callback<StripeCheckoutSuccess>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub on_processing: Callback<PaymentIntent>,
+    #[prop_or_default]
+    pub on_next_action: Callback<NextAction>,
+    #[prop_or_default]
+    pub poll_processing: Option<PollOptions>,
+    #[prop_or_default]
+    pub handle: Option<StripeCheckoutHandle>,
+    pub render: Callback<StripeCheckoutState, Html>,
+}
+
+/// Yew function component running the [`StripeCheckout`] flow without any
+/// markup of its own.
+///
+/// It loads Stripe.js, mounts the Payment Element and confirms the payment
+/// exactly like [`StripeCheckout`], but leaves every pixel to `render`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let render = Callback::from(|state: StripeCheckoutState| html! {
+///     <form class="box">
+///         { state.element.clone() }
+///         if let Some(err) = &state.error {
+///             <p class="help is-danger">{ &err.message }</p>
+///         }
+///         <button
+///             type="button"
+///             class={classes!("button", "is-primary", state.loading.then_some("is-loading"))}
+///             disabled={!state.ready || !state.complete}
+///             onclick={state.submit.reform(|_| ())}
+///         >{ "Pay" }</button>
+///     </form>
+/// });
+/// html! {
+///     <HeadlessStripeCheckout
+///         publishable_key="pk_test_123".to_string()
+///         client_secret={client_secret.clone()}
+///         {render}
+///     />
+/// }
+/// ```
+#[function_component(HeadlessStripeCheckout)]
+pub fn headless_stripe_checkout(props: &HeadlessStripeCheckoutProps) -> Html {
+    let stripe_js = use_stripejs_status(None);
+    let stripe_ready = stripe_js.status.is_ready();
+    let element_ready = use_state(|| false);
+    let error = use_state(|| None::<StripeError>);
     let loading = use_state(|| false);
+    let processing = use_state(|| false);
+    let complete = use_state(|| false);
+    let next_action = use_state(|| None::<NextAction>);
+    let result = use_state(|| None::<StripeCheckoutSuccess>);
+    let container = use_node_ref();
+    let slot = use_mut_ref(MountSlot::<MountedElement>::default);
+    // Cleared when the form unmounts or remounts for a new secret, so
+    // pending confirm/validate futures leave the form's state alone.
+    let live = use_mut_ref(|| Rc::new(Cell::new(true)));
+    let in_flight = use_mut_ref(|| false);
+    // Options changed while an element is mounting are applied once it settles.
+    let latest_pe_opts = use_mut_ref(|| None::<PaymentElementOptions>);
+    *latest_pe_opts.borrow_mut() = props.payment_element_options.clone();
 
-    // Mount Stripe Payment Element on load
+    // Report a blocked or timed-out Stripe.js once per failure.
     {
-        let state = state.clone();
+        let on_error = props.on_error.clone();
+        use_effect_with(stripe_js.status.clone(), move |status| {
+            if let StripeJsStatus::Failed(reason) = status {
+                on_error.emit(stripe_js_error(reason));
+            }
+            || ()
+        });
+    }
+
+    // Mount Stripe Payment Element once Stripe.js is ready. A new key, secret
+    // or Stripe options needs a fresh Elements group, so the old element is
+    // destroyed and a new one mounted in its place.
+    {
+        let element_ready = element_ready.clone();
         let error = error.clone();
-        let pk = props.publishable_key.clone();
-        let cs = props.client_secret.clone();
+        let next_action = next_action.clone();
+        let processing = processing.clone();
+        let loading = loading.clone();
+        let complete = complete.clone();
+        let result = result.clone();
+        let container = container.clone();
+        let slot = slot.clone();
+        let live = live.clone();
+        let on_error = props.on_error.clone();
+        let latest_pe_opts = latest_pe_opts.clone();
         let pe_opts = props.payment_element_options.clone();
-        use_effect_with(stripe_ready, move |ready| {
-            if *ready {
-                let state = state.clone();
-                let error = error.clone();
+        let key = MountKey {
+            publishable_key: props.publishable_key.clone(),
+            client_secret: props.client_secret.clone(),
+            stripe_options: props.stripe_options.clone(),
+        };
+        use_effect_with((stripe_ready, key), move |(ready, key)| {
+            let target = container.cast::<Element>();
+            let (plan, old) = if *ready && target.is_none() {
+                // `render` left out `state.element`. Don't record the key,
+                // so the next prop change tries again.
+                let e = missing_container();
+                on_error.emit(e.clone());
+                error.set(Some(e));
+                (MountPlan::Keep, None)
+            } else {
+                slot.borrow_mut().advance(*ready, key)
+            };
+            if plan != MountPlan::Keep {
+                if let Some(old) = old {
+                    old.destroy();
+                }
+                live.borrow().set(false);
+                *live.borrow_mut() = Rc::new(Cell::new(true));
+                element_ready.set(false);
+                error.set(None);
+                next_action.set(None);
+                processing.set(false);
+                loading.set(false);
+                complete.set(false);
+                result.set(None);
+            }
+            if let (MountPlan::Mount | MountPlan::Remount, Some(target)) = (plan, target) {
+                let alive = live.borrow().clone();
+                let key = key.clone();
                 wasm_bindgen_futures::spawn_local(async move {
                     let opts = ElementsOptions {
-                        client_secret: cs.clone(),
+                        client_secret: key.client_secret.clone(),
                         appearance: None,
                     };
-                    match mount_payment_element(&pk, opts, "#stripe-payment-element", pe_opts).await
-                    {
-                        Ok((stripe, elements, payment_element)) => {
-                            state.set(Some((stripe, elements, payment_element)))
+                    let client = StripeClient::from_options(
+                        &key.publishable_key,
+                        key.stripe_options.as_ref(),
+                    );
+                    let mounted = match client {
+                        Ok(client) => client
+                            .mount_element(opts, target, pe_opts.clone())
+                            .await
+                            .map(|(elements, payment_element)| {
+                                let listener = on_element_change(&payment_element, move |ev| {
+                                    if alive.get() {
+                                        complete.set(ev.complete)
+                                    }
+                                });
+                                MountedElement {
+                                    stripe: client.js().clone(),
+                                    elements,
+                                    payment_element,
+                                    listener,
+                                }
+                            }),
+                        Err(e) => Err(e),
+                    };
+                    match mounted.map(|m| slot.borrow_mut().settle(&key, m, pe_opts)) {
+                        Ok(Ok(())) => {
+                            let mut slot = slot.borrow_mut();
+                            if let Some((mounted, latest)) =
+                                slot.options_update(&latest_pe_opts.borrow())
+                            {
+                                let _ = update_payment_element(&mounted.payment_element, &latest);
+                            }
+                            element_ready.set(true);
                         }
-                        Err(e) => error.set(Some(e.message)),
+                        // Superseded by a newer secret or unmounted meanwhile.
+                        Ok(Err(stale)) => stale.destroy(),
+                        Err(e) if slot.borrow().is_current(&key) => error.set(Some(e)),
+                        Err(_) => {}
                     }
                 });
             }
@@ -159,186 +466,756 @@ pub fn stripe_checkout(props: &StripeCheckoutProps) -> Html {
         });
     }
 
-    let on_click = {
-        let state = state.clone();
+    // Destroy the Payment Element on unmount.
+    {
+        let slot = slot.clone();
+        let live = live.clone();
+        use_effect_with((), move |_| {
+            move || {
+                live.borrow().set(false);
+                if let Some(mounted) = slot.borrow_mut().clear() {
+                    mounted.destroy();
+                }
+            }
+        });
+    }
+
+    // Layout and other Payment Element options can change in place.
+    {
+        let slot = slot.clone();
+        use_effect_with(props.payment_element_options.clone(), move |pe_opts| {
+            if let Some((mounted, pe_opts)) = slot.borrow_mut().options_update(pe_opts) {
+                let _ = update_payment_element(&mounted.payment_element, &pe_opts);
+            }
+            || ()
+        });
+    }
+
+    // One submission flow, shared by `submit` and any attached handle.
+    let run_submit: Rc<dyn Fn() -> SubmitFuture> = {
+        let slot = slot.clone();
         let loading = loading.clone();
+        let processing = processing.clone();
+        let next_action = next_action.clone();
         let error = error.clone();
-        let on_success = props.on_success.clone();
-        let on_error = props.on_error.clone();
-        let cs = props.client_secret.clone();
+        let result = result.clone();
+        let in_flight = in_flight.clone();
+        let live = live.clone();
+        let props = props.clone();
 
-        Callback::from(move |_: MouseEvent| {
-            let cs = cs.clone();
-            if *loading {
-                return;
+        Rc::new(move || {
+            // Confirm with the secret the mounted Elements group was created for.
+            let Some((stripe, elements, cs)) = slot
+                .borrow()
+                .current()
+                .map(|(m, cs)| (m.stripe.clone(), m.elements.clone(), cs.to_string()))
+            else {
+                return Box::pin(async { Err(not_ready()) });
+            };
+            if *in_flight.borrow() {
+                return Box::pin(async { Err(already_submitting()) });
             }
-            if let Some((stripe, elements, _pe)) = &*state {
-                let stripe = stripe.clone();
-                let elements = elements.clone();
-                let loading = loading.clone();
-                let error = error.clone();
-                let on_success = on_success.clone();
-                let on_error = on_error.clone();
-                loading.set(true);
-                error.set(None);
+            let loading = loading.clone();
+            let error = error.clone();
+            let result = result.clone();
+            let processing = processing.clone();
+            let next_action = next_action.clone();
+            let in_flight = in_flight.clone();
+            let props = props.clone();
+            let params = props.confirm_params.clone().unwrap_or_default();
+            let alive = live.borrow().clone();
+            let on_processing = {
+                let processing = processing.clone();
+                let on_processing = props.on_processing.clone();
+                let alive = alive.clone();
+                Callback::from(move |intent: PaymentIntent| {
+                    if alive.get() {
+                        processing.set(true);
+                    }
+                    on_processing.emit(intent);
+                })
+            };
+            if let Err(err) = params.validate(props.payment_element_options.as_ref()) {
+                props.on_error.emit(err.clone());
+                error.set(Some(err.clone()));
+                return Box::pin(async move { Err(err) });
+            }
+            *in_flight.borrow_mut() = true;
+            loading.set(true);
+            error.set(None);
+            next_action.set(None);
+            // Held by the future, so a caller dropping it mid-flight still
+            // frees the form for the next submission.
+            let guard = SubmitGuard {
+                in_flight: in_flight.clone(),
+                loading: loading.clone(),
+                alive: alive.clone(),
+            };
 
-                wasm_bindgen_futures::spawn_local(async move {
+            Box::pin(async move {
+                // The app still hears the outcome if this form was torn down
+                // meanwhile; only the form's own state is left alone.
+                let outcome = async {
                     // 1) Validate & collect all card/payment details
-                    if let Err(err) = validate_payment_element(&elements).await {
-                        on_error.emit(err.clone());
-                        error.set(Some(err.message));
-                        loading.set(false);
-                        return;
-                    }
+                    validate_payment_element(&elements).await?;
 
                     // 2) Proceed with confirmPayment now that elements.submit() has run
-                    let params = ConfirmPaymentParams::default();
-                    match confirm_payment(&stripe, &elements, params, Some(cs.clone()), true).await
+                    if let PaymentResult::Error(e) =
+                        confirm_payment(&stripe, &elements, params, Some(cs.clone()), true).await
                     {
-                        PaymentResult::Success(_) => {
-                            // After confirm, retrieve the PaymentIntent details to inspect status and fields
-                            let stripe_js: JsValue = stripe.clone().into();
-                            let retrieve_fn = js_sys::Reflect::get(
-                                &stripe_js,
-                                &JsValue::from_str("retrievePaymentIntent"),
-                            )
-                            .expect("retrievePaymentIntent not found")
-                            .unchecked_into::<js_sys::Function>();
-                            let promise = retrieve_fn
-                                .call1(&stripe_js, &JsValue::from_str(&cs))
-                                .expect("failed to call retrievePaymentIntent")
-                                .unchecked_into::<js_sys::Promise>();
-                            match wasm_bindgen_futures::JsFuture::from(promise).await {
-                                Ok(result) => {
-                                    let pi_js = js_sys::Reflect::get(
-                                        &result,
-                                        &JsValue::from_str("paymentIntent"),
-                                    )
-                                    .expect("no paymentIntent");
-                                    let pi_json: serde_json::Value =
-                                        wasm_bindgen::JsValue::from(pi_js)
-                                            .into_serde()
-                                            .unwrap_or_default();
-                                    let status = pi_json
-                                        .get("status")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or_default();
-
-                                    if status == "succeeded" {
-                                        // Parse result values safely
-                                        let amount_cents = pi_json
-                                            .get("amount_received")
-                                            .and_then(|v| v.as_i64())
-                                            .or_else(|| {
-                                                pi_json.get("amount").and_then(|v| v.as_i64())
-                                            })
-                                            .unwrap_or(0);
-                                        let amount = amount_cents as f64 / 100.0;
-                                        let (last4, brand, receipt_url) = {
-                                            let charges = pi_json
-                                                .get("charges")
-                                                .and_then(|c| c.get("data"))
-                                                .and_then(|d| d.as_array());
-                                            let first = charges.and_then(|arr| arr.get(0));
-                                            let card = first
-                                                .and_then(|f| f.get("payment_method_details"))
-                                                .and_then(|pmd| pmd.get("card"));
-                                            let last4 = card
-                                                .and_then(|c| c.get("last4"))
-                                                .and_then(|v| v.as_str())
-                                                .map(|s| s.to_string());
-                                            let brand = card
-                                                .and_then(|c| c.get("brand"))
-                                                .and_then(|v| v.as_str())
-                                                .map(|s| s.to_string());
-                                            let receipt_url = first
-                                                .and_then(|f| f.get("receipt_url"))
-                                                .and_then(|v| v.as_str())
-                                                .map(|s| s.to_string());
-                                            (last4, brand, receipt_url)
-                                        };
-                                        let pi_id = pi_json
-                                            .get("id")
-                                            .and_then(|v| v.as_str())
-                                            .map(|s| s.to_string());
-                                        on_success.emit(StripeCheckoutSuccess {
-                                            amount,
-                                            last4,
-                                            brand,
-                                            receipt_url,
-                                            payment_intent_id: pi_id,
-                                        });
-                                    } else {
-                                        // Error, not succeeded
-                                        let last_payment_error = pi_json.get("last_payment_error");
-                                        let msg = last_payment_error
-                                            .and_then(|err| err.get("message"))
-                                            .and_then(|v| v.as_str())
-                                            .map(|s| s.to_string())
-                                            .unwrap_or_else(|| format!("Payment failed (status: {}). Please try another card.", status));
-                                        let error_type = last_payment_error
-                                            .and_then(|err| err.get("type"))
-                                            .and_then(|v| v.as_str())
-                                            .map(|s| s.to_string());
-                                        let code = last_payment_error
-                                            .and_then(|err| err.get("code"))
-                                            .and_then(|v| v.as_str())
-                                            .map(|s| s.to_string());
-                                        on_error.emit(StripeError {
-                                            message: msg.clone(),
-                                            error_type,
-                                            code,
-                                        });
-                                        error.set(Some(msg));
-                                    }
-                                }
-                                Err(e) => {
-                                    let msg = format!("Stripe API error: {:?}", e);
-                                    on_error.emit(StripeError {
-                                        message: msg.clone(),
-                                        error_type: Some("api_error".into()),
-                                        code: None,
-                                    });
-                                    error.set(Some(msg));
-                                }
-                            }
+                        return Err(e);
+                    }
+
+                    // 3) Retrieve the PaymentIntent details to inspect status and fields
+                    match resolve_checkout(
+                        &stripe,
+                        &cs,
+                        props.poll_processing.as_ref(),
+                        &on_processing,
+                    )
+                    .await
+                    {
+                        CheckoutOutcome::Succeeded(success) => {
+                            Ok(StripeCheckoutResult::Succeeded(success))
+                        }
+                        CheckoutOutcome::Processing(intent) => {
+                            Ok(StripeCheckoutResult::Processing(intent))
                         }
-                        PaymentResult::Error(e) => {
-                            on_error.emit(e.clone());
-                            error.set(Some(e.message));
+                        CheckoutOutcome::RequiresAction(action) => {
+                            Ok(StripeCheckoutResult::RequiresAction(action))
                         }
+                        CheckoutOutcome::Failed(e) => Err(e),
                     }
+                }
+                .await;
 
-                    loading.set(false);
-                });
-            }
+                drop(guard);
+                match &outcome {
+                    Ok(StripeCheckoutResult::Succeeded(success)) => {
+                        props.on_success.emit(success.clone());
+                        if alive.get() {
+                            processing.set(false);
+                            result.set(Some(success.clone()));
+                        }
+                    }
+                    // Pending message stays up; the payment settles later.
+                    Ok(StripeCheckoutResult::Processing(_)) => {}
+                    Ok(StripeCheckoutResult::RequiresAction(action)) => {
+                        props.on_next_action.emit(action.clone());
+                        if alive.get() {
+                            processing.set(false);
+                            next_action.set(Some(action.clone()));
+                        }
+                    }
+                    Err(e) => {
+                        props.on_error.emit(e.clone());
+                        if alive.get() {
+                            processing.set(false);
+                            error.set(Some(e.clone()));
+                        }
+                    }
+                }
+                outcome
+            })
         })
     };
 
-    html! {
-        <div class="flex flex-col gap-4 items-center w-full">
-            { for props.children.iter() }
-            <div id="stripe-payment-element" class="w-full mb-2" />
-            <button
-                type="button"
-                onclick={on_click}
-                disabled={!stripe_ready || *loading}
-                class="rounded bg-blue-600 text-white font-semibold px-5 py-2 shadow hover:bg-blue-700 transition disabled:opacity-50">
-                {
-                    if *loading {
-                        "Processing…".to_string()
-                    } else {
-                        props.button_label.clone().unwrap_or_else(|| "Pay Now".to_string())
-                    }
+    let run_validate: Rc<dyn Fn() -> ValidateFuture> = {
+        let slot = slot.clone();
+        let error = error.clone();
+        let live = live.clone();
+        Rc::new(move || {
+            let Some(elements) = slot.borrow().current().map(|(m, _)| m.elements.clone()) else {
+                return Box::pin(async { Err(not_ready()) });
+            };
+            let error = error.clone();
+            let alive = live.borrow().clone();
+            Box::pin(async move {
+                let checked = validate_payment_element(&elements).await;
+                if alive.get() {
+                    error.set(checked.as_ref().err().cloned());
                 }
-            </button>
-            {
-                if let Some(msg) = &*error {
-                    html!{ <div class="text-red-500 text-sm">{ msg }</div> }
-                } else {
-                    Html::default()
+                checked
+            })
+        })
+    };
+
+    let run_reset: Rc<dyn Fn()> = {
+        let slot = slot.clone();
+        let error = error.clone();
+        let next_action = next_action.clone();
+        let processing = processing.clone();
+        let result = result.clone();
+        let complete = complete.clone();
+        let loading = loading.clone();
+        let in_flight = in_flight.clone();
+        Rc::new(move || {
+            if let Some((mounted, _)) = slot.borrow().current() {
+                let _ = mounted.payment_element.clear();
+            }
+            error.set(None);
+            next_action.set(None);
+            processing.set(false);
+            result.set(None);
+            complete.set(false);
+            // A submission still in flight keeps the form busy until it ends.
+            loading.set(*in_flight.borrow());
+        })
+    };
+
+    // Keep the parent's handle pointed at this render's flow.
+    {
+        let controls = CheckoutControls {
+            submit: run_submit.clone(),
+            validate: run_validate,
+            reset: run_reset,
+        };
+        let handle = props.handle.clone();
+        let ready = *element_ready;
+        use_effect(move || {
+            if let (true, Some(handle)) = (ready, &handle) {
+                *handle.controls.borrow_mut() = Some(controls);
+            }
+            move || {
+                if let Some(handle) = handle {
+                    handle.controls.borrow_mut().take();
                 }
             }
-        </div>
+        });
+    }
+
+    let submit = Callback::from(move |_: ()| {
+        let run = run_submit.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = run().await;
+        });
+    });
+
+    props.render.emit(StripeCheckoutState {
+        ready: stripe_ready && *element_ready,
+        complete: *complete,
+        loading: *loading,
+        processing: *processing,
+        error: match &stripe_js.status {
+            StripeJsStatus::Failed(reason) => Some(stripe_js_error(reason)),
+            _ => (*error).clone(),
+        },
+        stripe_js: stripe_js.status.clone(),
+        next_action: (*next_action).clone(),
+        result: (*result).clone(),
+        submit,
+        retry_load: stripe_js.retry.clone(),
+        element: html! { <div ref={container} /> },
+    })
+}
+
+type SubmitFuture = Pin<Box<dyn Future<Output = Result<StripeCheckoutResult, StripeError>>>>;
+type ValidateFuture = Pin<Box<dyn Future<Output = Result<(), StripeError>>>>;
+
+/// How a submission through [`StripeCheckoutHandle::submit`] ended, short of failing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StripeCheckoutResult {
+    /// The payment succeeded.
+    Succeeded(StripeCheckoutSuccess),
+    /// The payment is still `processing` (not polled, or polling timed out).
+    Processing(Box<PaymentIntent>),
+    /// The customer must pay a voucher or scan a QR code.
+    RequiresAction(NextAction),
+}
+
+/// The actions a mounted checkout form registers with its handle.
+struct CheckoutControls {
+    submit: Rc<dyn Fn() -> SubmitFuture>,
+    validate: Rc<dyn Fn() -> ValidateFuture>,
+    reset: Rc<dyn Fn()>,
+}
+
+/// Drives a [`StripeCheckout`] or [`HeadlessStripeCheckout`] from its parent.
+///
+/// Create one with [`use_stripe_checkout`] and pass it as the form's `handle`
+/// prop. Until the form has mounted its Payment Element, `submit` and
+/// `validate` fail with a "not ready" [`StripeError`].
+///
+/// The form's own callbacks (`on_success`, `on_error`, …) and inline state
+/// still update when the payment is submitted through the handle.
+#[derive(Clone, Default)]
+pub struct StripeCheckoutHandle {
+    controls: Rc<RefCell<Option<CheckoutControls>>>,
+}
+
+impl PartialEq for StripeCheckoutHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.controls, &other.controls)
+    }
+}
+
+impl StripeCheckoutHandle {
+    /// Whether a form is attached and its Payment Element is mounted.
+    pub fn is_ready(&self) -> bool {
+        self.controls.borrow().is_some()
+    }
+
+    /// Validate, confirm and resolve the payment, exactly like the Pay button.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(StripeCheckoutResult)`: the payment succeeded, is processing, or needs
+    ///   the customer to act on a voucher/QR code.
+    /// - `Err(StripeError)`: validation or confirmation failed, the form isn't
+    ///   ready, or a submission is already in flight.
+    pub async fn submit(&self) -> Result<StripeCheckoutResult, StripeError> {
+        let submit = match self.controls.borrow().as_ref() {
+            Some(controls) => controls.submit.clone(),
+            None => return Err(not_ready()),
+        };
+        submit().await
+    }
+
+    /// Run `elements.submit()` to check the form without paying.
+    ///
+    /// # Returns
+    ///
+    /// `Err(StripeError)` with the first invalid field, also shown inline.
+    pub async fn validate(&self) -> Result<(), StripeError> {
+        let validate = match self.controls.borrow().as_ref() {
+            Some(controls) => controls.validate.clone(),
+            None => return Err(not_ready()),
+        };
+        validate().await
+    }
+
+    /// Clear the Payment Element's fields and any error, voucher or result shown.
+    pub fn reset(&self) {
+        let reset = self.controls.borrow().as_ref().map(|c| c.reset.clone());
+        if let Some(reset) = reset {
+            reset();
+        }
+    }
+}
+
+/// Custom hook: create a [`StripeCheckoutHandle`] that stays the same across renders.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let checkout = use_stripe_checkout();
+/// let place_order = {
+///     let checkout = checkout.clone();
+///     Callback::from(move |_| {
+///         let checkout = checkout.clone();
+///         spawn_local(async move {
+///             if checkout.validate().await.is_err() || save_order().await.is_err() {
+///                 return;
+///             }
+///             if let Ok(StripeCheckoutResult::Succeeded(s)) = checkout.submit().await {
+///                 show_receipt(s);
+///             }
+///         });
+///     })
+/// };
+/// html! {
+///     <>
+///         <HeadlessStripeCheckout
+///             publishable_key="pk_test_123".to_string()
+///             client_secret={client_secret.clone()}
+///             handle={checkout.clone()}
+///             render={Callback::from(|state: StripeCheckoutState| state.element)}
+///         />
+///         <button onclick={place_order}>{ "Place order" }</button>
+///     </>
+/// }
+/// ```
+#[hook]
+pub fn use_stripe_checkout() -> StripeCheckoutHandle {
+    (*use_memo((), |_| StripeCheckoutHandle::default())).clone()
+}
+
+/// Marks a submission as finished when dropped, whether the future ran to
+/// completion or was dropped by its caller.
+struct SubmitGuard {
+    in_flight: Rc<RefCell<bool>>,
+    loading: UseStateHandle<bool>,
+    alive: Rc<Cell<bool>>,
+}
+
+impl Drop for SubmitGuard {
+    fn drop(&mut self) {
+        *self.in_flight.borrow_mut() = false;
+        if self.alive.get() {
+            self.loading.set(false);
+        }
+    }
+}
+
+/// The props a Payment Element is mounted for. A change to any of them needs
+/// a fresh Elements group; `payment_element_options` are applied in place.
+#[derive(Clone, Debug, PartialEq)]
+struct MountKey {
+    publishable_key: String,
+    client_secret: String,
+    stripe_options: Option<StripeOptions>,
+}
+
+/// What the mount effect does when Stripe.js readiness or the [`MountKey`] changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MountPlan {
+    /// Nothing to do.
+    Keep,
+    /// Mount the first element.
+    Mount,
+    /// Destroy the current element and mount one for the new key.
+    Remount,
+    /// Destroy the current element.
+    Unmount,
+}
+
+/// The mounted Payment Element and the handles confirmation needs.
+struct MountedElement {
+    stripe: JsStripe,
+    elements: JsElements,
+    payment_element: JsPaymentElement,
+    listener: ElementChangeListener,
+}
+
+impl MountedElement {
+    fn destroy(self) {
+        drop(self.listener);
+        let _ = self.payment_element.destroy();
+    }
+}
+
+/// Which [`MountKey`] the form is mounted for, and the element (`T`) once
+/// mounting has finished, with the options it currently has.
+struct MountSlot<T> {
+    key: Option<MountKey>,
+    mounted: Option<T>,
+    options: Option<PaymentElementOptions>,
+}
+
+impl<T> Default for MountSlot<T> {
+    fn default() -> Self {
+        Self {
+            key: None,
+            mounted: None,
+            options: None,
+        }
+    }
+}
+
+impl<T> MountSlot<T> {
+    /// Move to `next`, returning what to do and the element to destroy, if any.
+    fn advance(&mut self, ready: bool, next: &MountKey) -> (MountPlan, Option<T>) {
+        let plan = match (&self.key, ready) {
+            (Some(current), true) if current == next => MountPlan::Keep,
+            (Some(_), true) => MountPlan::Remount,
+            (None, true) => MountPlan::Mount,
+            (Some(_), false) => MountPlan::Unmount,
+            (None, false) => MountPlan::Keep,
+        };
+        if plan == MountPlan::Keep {
+            return (plan, None);
+        }
+        self.key = ready.then(|| next.clone());
+        self.options = None;
+        (plan, self.mounted.take())
+    }
+
+    /// Whether `key` is the one the form is (being) mounted for.
+    fn is_current(&self, key: &MountKey) -> bool {
+        self.key.as_ref() == Some(key)
+    }
+
+    /// Record the element mounted for `key` with `options`, or hand it back
+    /// for destruction if the form moved on meanwhile.
+    fn settle(
+        &mut self,
+        key: &MountKey,
+        element: T,
+        options: Option<PaymentElementOptions>,
+    ) -> Result<(), T> {
+        if self.is_current(key) && self.mounted.is_none() {
+            self.mounted = Some(element);
+            self.options = options;
+            Ok(())
+        } else {
+            Err(element)
+        }
+    }
+
+    /// The mounted element and `latest`, if the element doesn't have those
+    /// options yet. Records them as applied.
+    fn options_update(
+        &mut self,
+        latest: &Option<PaymentElementOptions>,
+    ) -> Option<(&T, PaymentElementOptions)> {
+        let mounted = self.mounted.as_ref()?;
+        if self.options == *latest {
+            return None;
+        }
+        self.options = latest.clone();
+        Some((mounted, latest.clone()?))
+    }
+
+    /// The mounted element and the client secret it was created with.
+    fn current(&self) -> Option<(&T, &str)> {
+        Some((self.mounted.as_ref()?, &self.key.as_ref()?.client_secret))
+    }
+
+    /// Forget the key, returning the element to destroy.
+    fn clear(&mut self) -> Option<T> {
+        self.key = None;
+        self.options = None;
+        self.mounted.take()
+    }
+}
+
+fn stripe_js_error(reason: &str) -> StripeError {
+    StripeError {
+        message: reason.to_string(),
+        error_type: None,
+        code: None,
+    }
+}
+
+fn missing_container() -> StripeError {
+    StripeError {
+        message: "The payment form has no container; render `state.element` once.".into(),
+        error_type: None,
+        code: None,
+    }
+}
+
+fn not_ready() -> StripeError {
+    StripeError {
+        message: "The payment form is not ready yet.".into(),
+        error_type: None,
+        code: None,
+    }
+}
+
+fn already_submitting() -> StripeError {
+    StripeError {
+        message: "A payment is already being submitted.".into(),
+        error_type: None,
+        code: None,
+    }
+}
+
+/// How a checkout ended up, as reported to the app.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum CheckoutOutcome {
+    Succeeded(StripeCheckoutSuccess),
+    /// Still `processing`: not polled, or polling timed out.
+    Processing(Box<PaymentIntent>),
+    /// `requires_action` with a voucher or QR code for the customer.
+    RequiresAction(NextAction),
+    Failed(StripeError),
+}
+
+/// Retrieve the PaymentIntent behind `client_secret` and map it to the
+/// outcome `StripeCheckout` reports: [`StripeCheckoutSuccess`] when the
+/// intent `succeeded`, otherwise a [`StripeError`] built from
+/// `last_payment_error`.
+///
+/// A `processing` intent is passed to `on_processing`, then polled with
+/// `poll` if given.
+///
+/// Shared by [`StripeCheckout`] and [`StripeReturnHandler`](crate::StripeReturnHandler)
+/// so both report identical results.
+pub(crate) async fn resolve_checkout(
+    stripe: &JsStripe,
+    client_secret: &str,
+    poll: Option<&PollOptions>,
+    on_processing: &Callback<PaymentIntent>,
+) -> CheckoutOutcome {
+    let intent = match retrieve_payment_intent(stripe, client_secret).await {
+        Ok(intent) => intent,
+        Err(e) => return CheckoutOutcome::Failed(e),
+    };
+    if intent.status != PaymentIntentStatus::Processing {
+        return checkout_outcome(intent);
+    }
+    on_processing.emit(intent.clone());
+    match poll {
+        Some(poll) => match wait_for_terminal_status(stripe, client_secret, poll).await {
+            Ok(intent) => checkout_outcome(intent),
+            Err(e) => CheckoutOutcome::Failed(e),
+        },
+        None => CheckoutOutcome::Processing(Box::new(intent)),
+    }
+}
+
+/// Map a retrieved [`PaymentIntent`] to the outcome `StripeCheckout` reports.
+pub(crate) fn checkout_outcome(intent: PaymentIntent) -> CheckoutOutcome {
+    match (intent.status, intent.next_action) {
+        (PaymentIntentStatus::Succeeded, _) => {
+            let charge = intent.charges.and_then(|c| c.data.into_iter().next());
+            let card = charge
+                .as_ref()
+                .and_then(|c| c.payment_method_details.as_ref())
+                .and_then(|pmd| pmd.card.clone())
+                .unwrap_or_default();
+            let amount_cents = intent.amount_received.unwrap_or(intent.amount);
+            CheckoutOutcome::Succeeded(StripeCheckoutSuccess {
+                amount: amount_cents as f64 / 100.0,
+                last4: card.last4,
+                brand: card.brand,
+                receipt_url: charge.and_then(|c| c.receipt_url),
+                payment_intent_id: Some(intent.id),
+            })
+        }
+        (PaymentIntentStatus::Processing, next_action) => {
+            CheckoutOutcome::Processing(Box::new(PaymentIntent {
+                next_action,
+                ..intent
+            }))
+        }
+        (PaymentIntentStatus::RequiresAction, Some(action))
+            if !matches!(action, NextAction::Other { .. }) =>
+        {
+            CheckoutOutcome::RequiresAction(action)
+        }
+        // Error, not succeeded
+        (status, _) => {
+            CheckoutOutcome::Failed(intent.last_payment_error.unwrap_or_else(|| StripeError {
+                message: format!(
+                    "Payment failed (status: {}). Please try another card.",
+                    status.as_str()
+                ),
+                error_type: None,
+                code: None,
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(client_secret: &str) -> MountKey {
+        MountKey {
+            publishable_key: "pk_test_123".into(),
+            client_secret: client_secret.into(),
+            stripe_options: None,
+        }
+    }
+
+    #[test]
+    fn mounts_once_stripe_js_is_ready() {
+        let mut slot = MountSlot::<&str>::default();
+        assert_eq!(slot.advance(false, &key("cs_1")), (MountPlan::Keep, None));
+        assert_eq!(slot.advance(true, &key("cs_1")), (MountPlan::Mount, None));
+        assert!(slot.current().is_none());
+        assert_eq!(slot.settle(&key("cs_1"), "pe_1", None), Ok(()));
+        assert_eq!(slot.current(), Some((&"pe_1", "cs_1")));
+        assert_eq!(slot.advance(true, &key("cs_1")), (MountPlan::Keep, None));
+    }
+
+    #[test]
+    fn new_client_secret_destroys_old_element() {
+        let mut slot = MountSlot::default();
+        slot.advance(true, &key("cs_1"));
+        slot.settle(&key("cs_1"), "pe_1", None).unwrap();
+
+        assert_eq!(
+            slot.advance(true, &key("cs_2")),
+            (MountPlan::Remount, Some("pe_1"))
+        );
+        // Nothing to confirm against until the new element has mounted.
+        assert!(slot.current().is_none());
+    }
+
+    #[test]
+    fn confirm_uses_new_client_secret() {
+        let mut slot = MountSlot::default();
+        slot.advance(true, &key("cs_1"));
+        slot.settle(&key("cs_1"), "pe_1", None).unwrap();
+        slot.advance(true, &key("cs_2"));
+        slot.settle(&key("cs_2"), "pe_2", None).unwrap();
+
+        assert_eq!(slot.current(), Some((&"pe_2", "cs_2")));
+    }
+
+    #[test]
+    fn other_mount_props_remount_too() {
+        let mut slot = MountSlot::default();
+        slot.advance(true, &key("cs_1"));
+        slot.settle(&key("cs_1"), "pe_1", None).unwrap();
+
+        let new_key = MountKey {
+            publishable_key: "pk_test_456".into(),
+            ..key("cs_1")
+        };
+        assert_eq!(
+            slot.advance(true, &new_key),
+            (MountPlan::Remount, Some("pe_1"))
+        );
+
+        let with_options = MountKey {
+            stripe_options: Some(StripeOptions {
+                locale: Some("fr".into()),
+                ..Default::default()
+            }),
+            ..new_key.clone()
+        };
+        slot.settle(&new_key, "pe_2", None).unwrap();
+        assert_eq!(
+            slot.advance(true, &with_options),
+            (MountPlan::Remount, Some("pe_2"))
+        );
+    }
+
+    #[test]
+    fn superseded_mount_is_handed_back() {
+        let mut slot = MountSlot::default();
+        slot.advance(true, &key("cs_1"));
+        // The secret changes while the first element is still mounting.
+        assert_eq!(slot.advance(true, &key("cs_2")), (MountPlan::Remount, None));
+        assert_eq!(slot.settle(&key("cs_1"), "pe_1", None), Err("pe_1"));
+        assert!(!slot.is_current(&key("cs_1")));
+
+        assert_eq!(slot.settle(&key("cs_2"), "pe_2", None), Ok(()));
+        assert_eq!(slot.current(), Some((&"pe_2", "cs_2")));
+    }
+
+    #[test]
+    fn clear_returns_element_and_rejects_late_mounts() {
+        let mut slot = MountSlot::default();
+        slot.advance(true, &key("cs_1"));
+        slot.settle(&key("cs_1"), "pe_1", None).unwrap();
+
+        assert_eq!(slot.clear(), Some("pe_1"));
+        assert!(slot.current().is_none());
+        assert_eq!(slot.settle(&key("cs_1"), "late", None), Err("late"));
+    }
+
+    #[test]
+    fn options_changed_while_mounting_are_applied_after_settle() {
+        let opts = |layout: &str| {
+            Some(PaymentElementOptions {
+                layout: Some(layout.into()),
+                ..Default::default()
+            })
+        };
+        let mut slot = MountSlot::default();
+        slot.advance(true, &key("cs_1"));
+        // Nothing to update until the element has mounted.
+        assert_eq!(slot.options_update(&opts("accordion")), None);
+
+        slot.settle(&key("cs_1"), "pe_1", opts("tabs")).unwrap();
+        assert_eq!(
+            slot.options_update(&opts("accordion")),
+            Some((&"pe_1", opts("accordion").unwrap()))
+        );
+        assert_eq!(slot.options_update(&opts("accordion")), None);
+    }
+
+    #[test]
+    fn unchanged_options_are_not_reapplied() {
+        let mut slot = MountSlot::default();
+        slot.advance(true, &key("cs_1"));
+        slot.settle(&key("cs_1"), "pe_1", None).unwrap();
+        assert_eq!(slot.options_update(&None), None);
     }
 }
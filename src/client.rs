@@ -3,13 +3,34 @@
 //! High-level Rust API for integrating Stripe.js Payment Element in Yew applications.
 //!
 //! This module provides:
+//! - `StripeOptions` to set `locale`, `apiVersion`, `stripeAccount` and `betas` on the Stripe instance.
+//! - `load_stripe()` to load Stripe.js and create a `StripeClient` outside any component.
 //! - `ElementsOptions` to configure Stripe Elements with a PaymentIntent client secret.
 //! - `PaymentElementOptions` to customize layout and fields of the Payment Element.
-//! - `ConfirmPaymentParams` for passing parameters to `stripe.confirmPayment`, such as return URLs and save-card.
-//! - `mount_payment_element()` to asynchronously initialize Stripe, create Elements, and mount the Payment Element.
+//! - `ConfirmPaymentParams` for passing parameters to `stripe.confirmPayment`, such as return URLs,
+//!   billing details, shipping and `setup_future_usage`.
+//! - `mount_payment_element()` to asynchronously initialize Stripe, create Elements, and mount the Payment Element
+//!   into a CSS selector or DOM element (`MountTarget`).
 //! - `validate_payment_element()` to optionally validate form data before creating a PaymentIntent.
 //! - `confirm_payment()` to complete the payment flow with built-in SCA/3DS support.
 //! - `unmount_payment_element()` to tear down a mounted Payment Element for re-use.
+//! - `update_payment_element()` to change a mounted Payment Element's options in place.
+//! - `on_element_change()` to follow an element's `change` events (e.g. whether the form is complete).
+//! - `create_payment_method_messaging_element()` for BNPL promotional messaging.
+//! - `retrieve_payment_intent()` / `retrieve_setup_intent()` to check an intent's state as typed models.
+//! - `wait_for_terminal_status()` to poll a `processing` PaymentIntent until it settles.
+//! - `handle_next_action()` to finish an intent confirmed server-side that returned `requires_action`.
+//! - `collect_bank_account_for_payment()`, `confirm_us_bank_account_payment()` and
+//!   `verify_microdeposits_for_payment()` for US bank account (ACH) debits.
+//! - `collect_financial_connections_accounts()` to link bank accounts read-only.
+//! - `verify_identity()` to run a Stripe Identity verification.
+//! - `init_embedded_checkout()` to embed a Stripe Checkout Session in the page.
+//! - `init_checkout()` to build your own UI on a Checkout Session (Custom Checkout).
+//! - `load_connect_and_initialize()` to render Connect embedded components for a connected account.
+//! - `create_ephemeral_key_nonce()` and `create_issuing_element()` to display Issuing card details.
+//! - `create_elements()`, `create_payment_element()`, `create_address_element()` and
+//!   `create_link_authentication_element()` to compose several elements in one group.
+//! - `parse_return_url()` to read the query params Stripe appends after a redirect-based payment.
 //!
 //! # Cargo.toml
 //! ```toml
@@ -66,11 +87,11 @@
 //!                     // Tear down after a previous payment (if needed)
 //!                     unmount_payment_element(&pe);
 //!
-//!                     // Confirm new payment with save_card = true
+//!                     // Confirm new payment, saving the card for later
 //!                     let params = ConfirmPaymentParams {
 //!                         return_url: Some("https://…".into()),
-//!                         save_payment_method: Some(true),
-//!                         extra: None,
+//!                         setup_future_usage: Some(SetupFutureUsage::OffSession),
+//!                         ..Default::default()
 //!                     };
 //!                     match confirm_payment(&s, &e, params, None, true).await {
 //!                         PaymentResult::Success(info) => log::info!("Paid: {:?}", info),
@@ -90,12 +111,89 @@
 //! }
 //! ```
 
-use crate::bindings::{new_stripe, JsElements, JsPaymentElement, JsStripe};
-use serde::{Deserialize, Serialize};
-use serde_wasm_bindgen::{from_value, to_value};
-use wasm_bindgen::JsValue;
-use wasm_bindgen_futures::JsFuture;
-use web_sys::js_sys::{Object, Reflect};
+use crate::bindings::{
+    load_connect_and_initialize as init_connect, new_stripe, new_stripe_with_options, JsCheckout,
+    JsElements, JsEmbeddedCheckout, JsPaymentElement, JsStripe, JsStripeConnect,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, Serializer};
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::js_sys::{Date, Function, Object, Promise, Reflect};
+use web_sys::{Element, UrlSearchParams};
+
+/// Initialization options for `Stripe(publishableKey, options)`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct StripeOptions {
+    /// UI locale, e.g. `"fr"`, `"de"` or `"auto"` (Stripe's default).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// Pin the Stripe API version used by Stripe.js, e.g. `"2024-06-20"`.
+    #[serde(rename = "apiVersion", skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+
+    /// Connect: act on behalf of this connected account (`acct_…`).
+    #[serde(rename = "stripeAccount", skip_serializing_if = "Option::is_none")]
+    pub stripe_account: Option<String>,
+
+    /// Opt into Stripe.js beta features.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub betas: Option<Vec<String>>,
+}
+
+/// Create a Stripe.js instance, passing `options` to the constructor.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if the options cannot be serialized or Stripe.js
+/// rejects them.
+///
+pub fn create_stripe(
+    publishable_key: &str,
+    options: &StripeOptions,
+) -> Result<JsStripe, StripeError> {
+    let opts_js = to_value(options).map_err(serde_error_to_stripe_error)?;
+    new_stripe_with_options(publishable_key, opts_js).map_err(js_to_stripe_error)
+}
+
+/// Load Stripe.js if needed, then create a [`StripeClient`], like `loadStripe`
+/// from `@stripe/stripe-js`.
+///
+/// Uses the same loader as [`use_stripejs`](crate::use_stripejs), so it can
+/// run before or alongside any component.
+///
+/// # Arguments
+///
+/// * `publishable_key` – Your Stripe publishable key (starts with `pk_`).
+/// * `options` – Optional [`StripeOptions`] for the Stripe instance.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js fails to load or times out, or
+/// rejects the options.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// spawn_local(async move {
+///     match load_stripe("pk_test_123", None).await {
+///         Ok(client) => show(client.retrieve_payment_intent(&secret).await),
+///         Err(e) => log::error!("Stripe unavailable: {}", e.message),
+///     }
+/// });
+/// ```
+pub async fn load_stripe(
+    publishable_key: &str,
+    options: Option<&StripeOptions>,
+) -> Result<StripeClient, StripeError> {
+    crate::load_stripejs(None).await?;
+    StripeClient::from_options(publishable_key, options)
+}
 
 /// Configuration for `stripe.elements({ clientSecret, appearance })`.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -110,36 +208,299 @@ pub struct ElementsOptions {
 }
 
 /// Optional layout/customization for the mounted Payment Element.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct PaymentElementOptions {
     /// Layout mode: `"tabs"` or `"accordion"`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layout: Option<String>,
 
-    /// Any other JSON-serializable settings (e.g., fields).
+    /// Which fields the Payment Element collects, e.g. `billingDetails: "never"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<PaymentElementFields>,
+
+    /// Any other JSON-serializable settings, as a JSON object. Keys are
+    /// written after `layout` and `fields`, so they override them.
     #[serde(flatten)]
     pub extra: Option<serde_json::Value>,
 }
 
+/// The `fields` option of the Payment Element.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct PaymentElementFields {
+    /// Billing details collection. When any part is `"never"`, that data must be
+    /// passed in [`ConfirmPaymentParams::payment_method_data`] instead.
+    #[serde(rename = "billingDetails", skip_serializing_if = "Option::is_none")]
+    pub billing_details: Option<BillingDetailsFields>,
+}
+
+/// Whether the Payment Element collects a given field.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldCollection {
+    /// Stripe decides whether to collect the field (default).
+    Auto,
+    /// The field is never collected; you must supply it at confirmation.
+    Never,
+}
+
+/// `fields.billingDetails`: either one setting for everything or per-field settings.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum BillingDetailsFields {
+    /// `"auto"` or `"never"` for all billing details.
+    All(FieldCollection),
+    /// Per-field configuration.
+    Each {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<FieldCollection>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        email: Option<FieldCollection>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        phone: Option<FieldCollection>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        address: Option<AddressFields>,
+    },
+}
+
+/// `fields.billingDetails.address`: either one setting or per-line settings.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum AddressFields {
+    /// `"auto"`, `"never"` for the whole address.
+    All(FieldCollection),
+    /// Per-line configuration.
+    Each {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        line1: Option<FieldCollection>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        line2: Option<FieldCollection>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        city: Option<FieldCollection>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        state: Option<FieldCollection>,
+        #[serde(rename = "postalCode", skip_serializing_if = "Option::is_none")]
+        postal_code: Option<FieldCollection>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        country: Option<FieldCollection>,
+    },
+}
+
 /// Parameters for `stripe.confirmPayment({ confirmParams, ... })`.
+///
+/// Field names serialize to the snake_case keys Stripe.js expects.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct ConfirmPaymentParams {
     /// For redirect-based flows: where to send the customer on success.
-    #[serde(rename = "return_url", skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub return_url: Option<String>,
 
-    /// Whether to save the payment method for off-session use.
-    #[serde(
-        rename = "save_payment_method",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub save_payment_method: Option<bool>,
+    /// Shipping information for the PaymentIntent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping: Option<ShippingDetails>,
+
+    /// Data attached to the new PaymentMethod, e.g. billing details the
+    /// Payment Element was told not to collect.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_method_data: Option<PaymentMethodData>,
+
+    /// Email address that the receipt for the resulting payment is sent to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_email: Option<String>,
+
+    /// Fields of the returned PaymentIntent to expand, e.g. `"payment_method"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<Vec<String>>,
+
+    /// Save the payment method for future use, if your integration allows it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setup_future_usage: Option<SetupFutureUsage>,
 
-    /// Any additional confirm params (e.g. shipping info).
+    /// Any additional confirm params not modelled above. Must be a JSON
+    /// object; [`validate`](Self::validate) rejects keys that repeat a typed
+    /// field which is also set.
     #[serde(flatten)]
     pub extra: Option<serde_json::Value>,
 }
 
+/// A postal address, as used in billing and shipping details.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Address {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line2: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    /// Two-letter ISO country code, e.g. `"US"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+}
+
+/// Billing details attached to a PaymentMethod.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct BillingDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+}
+
+/// `confirmParams.payment_method_data`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct PaymentMethodData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing_details: Option<BillingDetails>,
+}
+
+/// `confirmParams.shipping`. `name` and `address` are required by Stripe.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ShippingDetails {
+    pub name: String,
+    pub address: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carrier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracking_number: Option<String>,
+}
+
+/// `confirmParams.setup_future_usage`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupFutureUsage {
+    /// Customer may be charged later without being present.
+    OffSession,
+    /// Customer will be present for later payments.
+    OnSession,
+}
+
+impl ConfirmPaymentParams {
+    /// Check that every billing detail the Payment Element was told to
+    /// `"never"` collect is supplied in `payment_method_data.billing_details`,
+    /// and that `extra` does not repeat a typed field that is also set.
+    ///
+    /// Stripe.js rejects the confirmation otherwise; checking up front gives
+    /// a clear error before any network call.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `validation_error` [`StripeError`] naming the first missing
+    /// or duplicated field.
+    pub fn validate(&self, pe_options: Option<&PaymentElementOptions>) -> Result<(), StripeError> {
+        if let Some(extra) = self.extra.as_ref().and_then(|e| e.as_object()) {
+            let typed = [
+                ("return_url", self.return_url.is_some()),
+                ("shipping", self.shipping.is_some()),
+                ("payment_method_data", self.payment_method_data.is_some()),
+                ("receipt_email", self.receipt_email.is_some()),
+                ("expand", self.expand.is_some()),
+                ("setup_future_usage", self.setup_future_usage.is_some()),
+            ];
+            if let Some((key, _)) = typed
+                .into_iter()
+                .find(|(key, set)| *set && extra.contains_key(*key))
+            {
+                return Err(StripeError {
+                    message: format!("`{key}` is set both as a field and in `extra`"),
+                    error_type: Some("validation_error".into()),
+                    code: Some("duplicate_param".into()),
+                });
+            }
+        }
+
+        let Some(fields) = pe_options
+            .and_then(|o| o.fields.as_ref())
+            .and_then(|f| f.billing_details.as_ref())
+        else {
+            return Ok(());
+        };
+        let never = |f: &Option<FieldCollection>| *f == Some(FieldCollection::Never);
+        let (name, email, phone, address) = match fields {
+            BillingDetailsFields::All(c) => {
+                let all = *c == FieldCollection::Never;
+                (all, all, all, [all; 6])
+            }
+            BillingDetailsFields::Each {
+                name,
+                email,
+                phone,
+                address,
+            } => {
+                let address = match address {
+                    Some(AddressFields::All(c)) => [*c == FieldCollection::Never; 6],
+                    Some(AddressFields::Each {
+                        line1,
+                        line2,
+                        city,
+                        state,
+                        postal_code,
+                        country,
+                    }) => [
+                        never(line1),
+                        never(line2),
+                        never(city),
+                        never(state),
+                        never(postal_code),
+                        never(country),
+                    ],
+                    None => [false; 6],
+                };
+                (never(name), never(email), never(phone), address)
+            }
+        };
+
+        let bd = self
+            .payment_method_data
+            .as_ref()
+            .and_then(|d| d.billing_details.as_ref());
+        let addr = bd.and_then(|b| b.address.as_ref());
+        // Hidden address lines must be present, though `line2` and `state`
+        // may legitimately be empty strings.
+        let has_line =
+            |get: fn(&Address) -> &Option<String>| addr.and_then(|a| get(a).as_ref()).is_some();
+
+        let required = [
+            ("name", name, bd.and_then(|b| b.name.as_ref()).is_some()),
+            ("email", email, bd.and_then(|b| b.email.as_ref()).is_some()),
+            ("phone", phone, bd.and_then(|b| b.phone.as_ref()).is_some()),
+            ("address.line1", address[0], has_line(|a| &a.line1)),
+            ("address.line2", address[1], has_line(|a| &a.line2)),
+            ("address.city", address[2], has_line(|a| &a.city)),
+            ("address.state", address[3], has_line(|a| &a.state)),
+            (
+                "address.postal_code",
+                address[4],
+                has_line(|a| &a.postal_code),
+            ),
+            ("address.country", address[5], has_line(|a| &a.country)),
+        ];
+
+        match required
+            .into_iter()
+            .find(|(_, hidden, present)| *hidden && !present)
+        {
+            None => Ok(()),
+            Some((field, _, _)) => Err(StripeError {
+                message: format!(
+                    "`fields.billingDetails` hides {field}, so \
+                     `payment_method_data.billing_details.{field}` must be provided"
+                ),
+                error_type: Some("validation_error".into()),
+                code: Some("billing_details_required".into()),
+            }),
+        }
+    }
+}
+
 /// Minimal representation of a confirmed PaymentIntent.
 #[derive(Clone, Debug, PartialEq)]
 pub struct PaymentIntentInfo {
@@ -149,6 +510,340 @@ pub struct PaymentIntentInfo {
     pub status: String,
 }
 
+/// A PaymentIntent as returned to the browser by Stripe.js.
+///
+/// Only the fields exposed to publishable keys are modelled.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct PaymentIntent {
+    /// Identifier, e.g. `pi_1Fxxxxxx`.
+    pub id: String,
+    pub status: PaymentIntentStatus,
+    /// Amount intended to be collected, in the smallest currency unit.
+    #[serde(default)]
+    pub amount: i64,
+    /// Amount that was collected, when known.
+    #[serde(default)]
+    pub amount_received: Option<i64>,
+    /// Three-letter ISO currency code, e.g. `"usd"`.
+    #[serde(default)]
+    pub currency: String,
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub receipt_email: Option<String>,
+    /// The error that caused the last attempt to fail, if any.
+    #[serde(default)]
+    pub last_payment_error: Option<StripeError>,
+    /// PaymentMethod id, or the object itself when expanded.
+    #[serde(default)]
+    pub payment_method: Option<serde_json::Value>,
+    #[serde(default)]
+    pub payment_method_types: Vec<String>,
+    /// What the customer must do next, when `status` is `requires_action`.
+    #[serde(default)]
+    pub next_action: Option<NextAction>,
+    /// Charges on older API versions; absent on newer ones.
+    #[serde(default)]
+    pub charges: Option<ChargeList>,
+    #[serde(default)]
+    pub livemode: bool,
+}
+
+/// `PaymentIntent.status`.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentIntentStatus {
+    RequiresPaymentMethod,
+    RequiresConfirmation,
+    RequiresAction,
+    Processing,
+    RequiresCapture,
+    Canceled,
+    Succeeded,
+    /// A status added by Stripe after this crate was released.
+    #[serde(other)]
+    Unknown,
+}
+
+impl PaymentIntentStatus {
+    /// The snake_case name Stripe uses, e.g. `"requires_action"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RequiresPaymentMethod => "requires_payment_method",
+            Self::RequiresConfirmation => "requires_confirmation",
+            Self::RequiresAction => "requires_action",
+            Self::Processing => "processing",
+            Self::RequiresCapture => "requires_capture",
+            Self::Canceled => "canceled",
+            Self::Succeeded => "succeeded",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// A SetupIntent as returned to the browser by Stripe.js.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct SetupIntent {
+    /// Identifier, e.g. `seti_1Fxxxxxx`.
+    pub id: String,
+    pub status: SetupIntentStatus,
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// `"off_session"` or `"on_session"`.
+    #[serde(default)]
+    pub usage: Option<String>,
+    /// The error that caused the last attempt to fail, if any.
+    #[serde(default)]
+    pub last_setup_error: Option<StripeError>,
+    /// PaymentMethod id, or the object itself when expanded.
+    #[serde(default)]
+    pub payment_method: Option<serde_json::Value>,
+    #[serde(default)]
+    pub payment_method_types: Vec<String>,
+    /// What the customer must do next, when `status` is `requires_action`.
+    #[serde(default)]
+    pub next_action: Option<NextAction>,
+    #[serde(default)]
+    pub livemode: bool,
+}
+
+/// `SetupIntent.status`.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupIntentStatus {
+    RequiresPaymentMethod,
+    RequiresConfirmation,
+    RequiresAction,
+    Processing,
+    Canceled,
+    Succeeded,
+    /// A status added by Stripe after this crate was released.
+    #[serde(other)]
+    Unknown,
+}
+
+impl SetupIntentStatus {
+    /// The snake_case name Stripe uses, e.g. `"requires_action"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RequiresPaymentMethod => "requires_payment_method",
+            Self::RequiresConfirmation => "requires_confirmation",
+            Self::RequiresAction => "requires_action",
+            Self::Processing => "processing",
+            Self::Canceled => "canceled",
+            Self::Succeeded => "succeeded",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// What the customer must do to complete a `requires_action` intent.
+///
+/// Deserialized from Stripe's `{ type, <type>: { … } }` shape; types this
+/// crate does not model are kept as [`NextAction::Other`].
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(try_from = "serde_json::Value")]
+pub enum NextAction {
+    /// Send the customer to `url` to authenticate.
+    RedirectToUrl(RedirectToUrl),
+    /// OXXO cash voucher (Mexico).
+    DisplayOxxoDetails(OxxoDisplayDetails),
+    /// Boleto bancário voucher (Brazil).
+    BoletoDisplayDetails(BoletoDisplayDetails),
+    /// Konbini convenience-store payment codes (Japan).
+    KonbiniDisplayDetails(KonbiniDisplayDetails),
+    /// PayNow QR code (Singapore).
+    PaynowDisplayQrCode(QrCodeDetails),
+    /// PromptPay QR code (Thailand).
+    PromptpayDisplayQrCode(QrCodeDetails),
+    /// WeChat Pay QR code.
+    WechatPayDisplayQrCode(QrCodeDetails),
+    /// US bank account awaiting microdeposit verification.
+    VerifyWithMicrodeposits(VerifyWithMicrodeposits),
+    /// Any other `type`, with the raw `next_action` object.
+    Other {
+        kind: String,
+        raw: serde_json::Value,
+    },
+}
+
+impl TryFrom<serde_json::Value> for NextAction {
+    type Error = serde_json::Error;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        let kind = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let details = value
+            .get(&kind)
+            .filter(|d| d.is_object())
+            .cloned()
+            .unwrap_or_else(|| serde_json::Value::Object(Default::default()));
+        Ok(match kind.as_str() {
+            "redirect_to_url" => Self::RedirectToUrl(serde_json::from_value(details)?),
+            "display_oxxo_details" => Self::DisplayOxxoDetails(serde_json::from_value(details)?),
+            "boleto_display_details" => {
+                Self::BoletoDisplayDetails(serde_json::from_value(details)?)
+            }
+            "konbini_display_details" => {
+                Self::KonbiniDisplayDetails(serde_json::from_value(details)?)
+            }
+            "paynow_display_qr_code" => Self::PaynowDisplayQrCode(serde_json::from_value(details)?),
+            "promptpay_display_qr_code" => {
+                Self::PromptpayDisplayQrCode(serde_json::from_value(details)?)
+            }
+            "wechat_pay_display_qr_code" => {
+                Self::WechatPayDisplayQrCode(serde_json::from_value(details)?)
+            }
+            "verify_with_microdeposits" => {
+                Self::VerifyWithMicrodeposits(serde_json::from_value(details)?)
+            }
+            _ => Self::Other { kind, raw: value },
+        })
+    }
+}
+
+/// `next_action.redirect_to_url`.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct RedirectToUrl {
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub return_url: Option<String>,
+}
+
+/// `next_action.display_oxxo_details`.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct OxxoDisplayDetails {
+    /// Unix timestamp after which the voucher expires.
+    #[serde(default)]
+    pub expires_after: Option<i64>,
+    #[serde(default)]
+    pub hosted_voucher_url: Option<String>,
+    /// The voucher reference number.
+    #[serde(default)]
+    pub number: Option<String>,
+}
+
+/// `next_action.boleto_display_details`.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct BoletoDisplayDetails {
+    /// Unix timestamp after which the boleto expires.
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    pub hosted_voucher_url: Option<String>,
+    /// The boleto barcode number.
+    #[serde(default)]
+    pub number: Option<String>,
+    /// Link to a printable PDF of the boleto.
+    #[serde(default)]
+    pub pdf: Option<String>,
+}
+
+/// `next_action.konbini_display_details`.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct KonbiniDisplayDetails {
+    /// Unix timestamp after which the payment codes expire.
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    pub hosted_voucher_url: Option<String>,
+    #[serde(default)]
+    pub stores: KonbiniStores,
+}
+
+/// Payment codes per konbini chain.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct KonbiniStores {
+    #[serde(default)]
+    pub familymart: Option<KonbiniStore>,
+    #[serde(default)]
+    pub lawson: Option<KonbiniStore>,
+    #[serde(default)]
+    pub ministop: Option<KonbiniStore>,
+    #[serde(default)]
+    pub seicomart: Option<KonbiniStore>,
+}
+
+/// Codes the customer gives the cashier at one konbini chain.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct KonbiniStore {
+    #[serde(default)]
+    pub confirmation_number: Option<String>,
+    #[serde(default)]
+    pub payment_code: Option<String>,
+}
+
+/// QR code details shared by PayNow, PromptPay and WeChat Pay.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct QrCodeDetails {
+    /// The raw data encoded in the QR code.
+    #[serde(default)]
+    pub data: Option<String>,
+    /// Stripe-hosted page with payment instructions.
+    #[serde(default)]
+    pub hosted_instructions_url: Option<String>,
+    /// Inline `data:` URL of the QR image (WeChat Pay).
+    #[serde(default)]
+    pub image_data_url: Option<String>,
+    #[serde(default)]
+    pub image_url_png: Option<String>,
+    #[serde(default)]
+    pub image_url_svg: Option<String>,
+}
+
+/// `next_action.verify_with_microdeposits`.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct VerifyWithMicrodeposits {
+    /// Unix timestamp when the microdeposits should arrive.
+    #[serde(default)]
+    pub arrival_date: Option<i64>,
+    /// Stripe-hosted page where the customer can verify instead.
+    #[serde(default)]
+    pub hosted_verification_url: Option<String>,
+    /// `"amounts"` or `"descriptor_code"`.
+    #[serde(default)]
+    pub microdeposit_type: Option<String>,
+}
+
+/// `PaymentIntent.charges` (older API versions only).
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ChargeList {
+    #[serde(default)]
+    pub data: Vec<Charge>,
+}
+
+/// The subset of a Charge useful for a receipt screen.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Charge {
+    pub id: String,
+    #[serde(default)]
+    pub receipt_url: Option<String>,
+    #[serde(default)]
+    pub payment_method_details: Option<PaymentMethodDetails>,
+}
+
+/// `Charge.payment_method_details`.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct PaymentMethodDetails {
+    #[serde(default)]
+    pub card: Option<CardDetails>,
+}
+
+/// Card brand and last four digits.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct CardDetails {
+    #[serde(default)]
+    pub brand: Option<String>,
+    #[serde(default)]
+    pub last4: Option<String>,
+}
+
 /// Strongly-typed outcome of attempting to confirm a payment.
 #[derive(Clone, Debug, PartialEq)]
 pub enum PaymentResult {
@@ -171,13 +866,59 @@ pub struct StripeError {
     pub code: Option<String>,
 }
 
+/// Where to mount an Element: a CSS selector or a DOM element.
+///
+/// Pass a `web_sys::Element` (e.g. from a Yew `NodeRef`) so several
+/// components can mount on the same page without sharing an id.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MountTarget {
+    /// A CSS selector, e.g. `"#payment-element"`.
+    Selector(String),
+    /// A DOM element.
+    Element(Element),
+}
+
+impl MountTarget {
+    fn mount(&self, element: &JsPaymentElement) -> Result<(), StripeError> {
+        match self {
+            Self::Selector(selector) => element.mount(selector),
+            Self::Element(target) => element.mount_element(target),
+        }
+        .map_err(js_to_stripe_error)
+    }
+}
+
+impl From<&str> for MountTarget {
+    fn from(selector: &str) -> Self {
+        Self::Selector(selector.to_string())
+    }
+}
+
+impl From<String> for MountTarget {
+    fn from(selector: String) -> Self {
+        Self::Selector(selector)
+    }
+}
+
+impl From<Element> for MountTarget {
+    fn from(element: Element) -> Self {
+        Self::Element(element)
+    }
+}
+
+impl From<&Element> for MountTarget {
+    fn from(element: &Element) -> Self {
+        Self::Element(element.clone())
+    }
+}
+
 /// Initialize Stripe.js, create an Elements instance, and mount a PaymentElement.
 ///
 /// # Arguments
 ///
 /// * `publishable_key` – Your Stripe publishable key (starts with `pk_`).
 /// * `elements_options` – Must include `client_secret`.
-/// * `mount_id` – CSS selector or DOM id, e.g. `"#payment-element"`.
+/// * `target` – CSS selector (e.g. `"#payment-element"`) or a DOM element.
 /// * `pe_options` – Optional layout/customization.
 ///
 /// # Returns
@@ -188,7 +929,7 @@ pub struct StripeError {
 pub async fn mount_payment_element(
     publishable_key: &str,
     elements_options: ElementsOptions,
-    mount_id: &str,
+    target: impl Into<MountTarget>,
     pe_options: Option<PaymentElementOptions>,
 ) -> Result<(JsStripe, JsElements, JsPaymentElement), StripeError> {
     // 1) Create Stripe instance
@@ -199,17 +940,16 @@ pub async fn mount_payment_element(
     let elements = stripe.elements(opts_js).map_err(js_to_stripe_error)?;
 
     // 3) Build JS args for create("payment", ...)
-    let pe_opts_js = pe_options
-        .map(|opts| to_value(&opts).expect("PaymentElementOptions serialization failed"))
-        .unwrap_or(JsValue::undefined());
+    let pe_opts_js = match pe_options {
+        Some(opts) => to_value(&opts).map_err(serde_error_to_stripe_error)?,
+        None => JsValue::undefined(),
+    };
     let payment_element = elements
         .create_element("payment", pe_opts_js)
         .map_err(js_to_stripe_error)?;
 
     // 4) Mount into DOM
-    payment_element
-        .mount(mount_id)
-        .map_err(js_to_stripe_error)?;
+    target.into().mount(&payment_element)?;
 
     Ok((stripe, elements, payment_element))
 }
@@ -259,7 +999,10 @@ pub async fn confirm_payment(
     } else {
         Reflect::set(&opts, &JsValue::from_str("elements"), elements.as_ref()).unwrap();
     }
-    let params_js = to_value(&params).expect("ConfirmPaymentParams serialization failed");
+    let params_js = match to_value(&params) {
+        Ok(v) => v,
+        Err(e) => return PaymentResult::Error(serde_error_to_stripe_error(e)),
+    };
     Reflect::set(&opts, &JsValue::from_str("confirmParams"), &params_js).unwrap();
     if redirect_if_required {
         Reflect::set(
@@ -279,21 +1022,24 @@ pub async fn confirm_payment(
     // Await the JS Promise
     match JsFuture::from(promise).await {
         Ok(js_val) => {
-            // Try to deserialize into StripeError first
-            if let Ok(err) = from_value::<StripeError>(js_val.clone()) {
-                return PaymentResult::Error(err);
+            // Declines and validation failures resolve as `{ error }`.
+            let error =
+                Reflect::get(&js_val, &JsValue::from_str("error")).unwrap_or(JsValue::UNDEFINED);
+            if !error.is_undefined() && !error.is_null() {
+                return PaymentResult::Error(js_to_stripe_error(error));
             }
             // Otherwise extract PaymentIntent info
             let intent = Reflect::get(&js_val, &JsValue::from_str("paymentIntent"))
-                .ok()
-                .and_then(|pi| Reflect::get(&pi, &JsValue::from_str("id")).ok())
-                .and_then(|v| v.as_string())
-                .unwrap_or_default();
-            let status = Reflect::get(&js_val, &JsValue::from_str("status"))
-                .ok()
-                .and_then(|v| v.as_string())
-                .unwrap_or_else(|| "succeeded".into());
-            PaymentResult::Success(PaymentIntentInfo { id: intent, status })
+                .unwrap_or(JsValue::UNDEFINED);
+            let field = |name: &str| {
+                Reflect::get(&intent, &JsValue::from_str(name))
+                    .ok()
+                    .and_then(|v| v.as_string())
+            };
+            PaymentResult::Success(PaymentIntentInfo {
+                id: field("id").unwrap_or_default(),
+                status: field("status").unwrap_or_else(|| "succeeded".into()),
+            })
         }
         Err(js_err) => PaymentResult::Error(js_to_stripe_error(js_err)),
     }
@@ -309,34 +1055,1681 @@ pub fn unmount_payment_element(payment_element: &JsPaymentElement) -> Result<(),
     payment_element.unmount().map_err(js_to_stripe_error)
 }
 
-/// Manually trigger off-session 3DS/SCA challenges.
-///
-/// # Arguments
-///
-/// * `stripe` – Your `JsStripe` instance.
-/// * `client_secret` – The PaymentIntent client secret for off-session flows.
+/// Apply new [`PaymentElementOptions`] to a mounted PaymentElement in place.
 ///
 /// # Errors
 ///
-/// Returns `Err(StripeError)` if Stripe.js rejects.
+/// Returns `Err(StripeError)` if Stripe.js rejects the options.
 ///
-pub async fn handle_card_action(stripe: &JsStripe, client_secret: &str) -> Result<(), StripeError> {
-    let promise = stripe
-        .handle_card_action(client_secret)
-        .map_err(js_to_stripe_error)?;
-    JsFuture::from(promise)
-        .await
-        .map(|_| ())
-        .map_err(js_to_stripe_error)
+pub fn update_payment_element(
+    payment_element: &JsPaymentElement,
+    options: &PaymentElementOptions,
+) -> Result<(), StripeError> {
+    let opts = to_value(options).map_err(serde_error_to_stripe_error)?;
+    payment_element.update(opts).map_err(js_to_stripe_error)
 }
 
-/// Convert any caught `JsValue` into a `StripeError` with best effort.
-fn js_to_stripe_error(value: JsValue) -> StripeError {
-    from_value::<StripeError>(value.clone()).unwrap_or_else(|_| StripeError {
-        message: value.as_string().unwrap_or_else(|| format!("{:?}", value)),
-        error_type: None,
-        code: None,
-    })
+/// Payload of a Stripe Element's `change` event.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ElementChangeEvent {
+    /// Every required field is filled in and valid.
+    #[serde(default)]
+    pub complete: bool,
+    /// No field has a value yet.
+    #[serde(default)]
+    pub empty: bool,
+    /// The accordion layout is collapsed.
+    #[serde(default)]
+    pub collapsed: bool,
+    /// The selected payment method, e.g. `{ "type": "card" }`.
+    #[serde(default)]
+    pub value: Option<serde_json::Value>,
+}
+
+/// A `change` handler registered with [`on_element_change`]; removed from
+/// the element when dropped.
+pub struct ElementChangeListener {
+    element: JsPaymentElement,
+    closure: Closure<dyn FnMut(JsValue)>,
+}
+
+impl Drop for ElementChangeListener {
+    fn drop(&mut self) {
+        self.element
+            .off("change", self.closure.as_ref().unchecked_ref());
+    }
+}
+
+/// Call `handler` with a typed [`ElementChangeEvent`] whenever the element changes.
+///
+/// # Returns
+///
+/// An [`ElementChangeListener`]; keep it alive for as long as you want events.
+///
+pub fn on_element_change(
+    element: &JsPaymentElement,
+    mut handler: impl FnMut(ElementChangeEvent) + 'static,
+) -> ElementChangeListener {
+    let closure = Closure::wrap(Box::new(move |event: JsValue| {
+        if let Ok(event) = from_value(event) {
+            handler(event);
+        }
+    }) as Box<dyn FnMut(JsValue)>);
+    element.on("change", closure.as_ref().unchecked_ref());
+    ElementChangeListener {
+        element: element.clone(),
+        closure,
+    }
+}
+
+/// Options for the Payment Method Messaging Element.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentMethodMessagingOptions {
+    /// Price in the currency's smallest unit, e.g. `4999` for $49.99.
+    pub amount: i64,
+    /// Three-letter ISO currency code, e.g. `"usd"`.
+    pub currency: String,
+    /// BNPL methods to promote, e.g. `["klarna", "afterpay_clearpay", "affirm"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub payment_method_types: Vec<String>,
+    /// The customer's two-letter country code, e.g. `"US"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+}
+
+/// Create a Payment Method Messaging Element.
+///
+/// Needs no client secret: it builds its own `stripe.elements()` instance.
+/// Mount the returned element with `mount` or `mount_element`, and change
+/// the amount later with [`update_payment_method_messaging_element`].
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects the options.
+///
+pub fn create_payment_method_messaging_element(
+    stripe: &JsStripe,
+    options: &PaymentMethodMessagingOptions,
+) -> Result<JsPaymentElement, StripeError> {
+    let elements = stripe
+        .elements(Object::new().into())
+        .map_err(js_to_stripe_error)?;
+    let opts = to_value(options).map_err(serde_error_to_stripe_error)?;
+    elements
+        .create_element("paymentMethodMessaging", opts)
+        .map_err(js_to_stripe_error)
+}
+
+/// Update a Payment Method Messaging Element in place, e.g. after the
+/// customer changes the quantity.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects the options.
+///
+pub fn update_payment_method_messaging_element(
+    element: &JsPaymentElement,
+    options: &PaymentMethodMessagingOptions,
+) -> Result<(), StripeError> {
+    let opts = to_value(options).map_err(serde_error_to_stripe_error)?;
+    element.update(opts).map_err(js_to_stripe_error)
+}
+
+/// Manually trigger off-session 3DS/SCA challenges.
+///
+/// Legacy: the result is discarded. Prefer [`handle_next_action`], which
+/// returns the updated intent.
+///
+/// # Arguments
+///
+/// * `stripe` – Your `JsStripe` instance.
+/// * `client_secret` – The PaymentIntent client secret for off-session flows.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects.
+///
+pub async fn handle_card_action(stripe: &JsStripe, client_secret: &str) -> Result<(), StripeError> {
+    let promise = stripe
+        .handle_card_action(client_secret)
+        .map_err(js_to_stripe_error)?;
+    JsFuture::from(promise)
+        .await
+        .map(|_| ())
+        .map_err(js_to_stripe_error)
+}
+
+/// Either kind of intent, as returned by [`handle_next_action`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Intent {
+    Payment(PaymentIntent),
+    Setup(SetupIntent),
+}
+
+/// Options for `stripe.handleNextAction`.
+#[derive(Serialize)]
+struct HandleNextActionOptions<'a> {
+    #[serde(rename = "clientSecret")]
+    client_secret: &'a str,
+}
+
+/// Handle the `next_action` of an intent your backend confirmed.
+///
+/// Call this after a server-side confirmation returns `requires_action`.
+/// Stripe.js runs 3DS, redirects or displays the voucher/QR code as needed.
+///
+/// # Arguments
+///
+/// * `stripe` – Your `JsStripe` instance.
+/// * `client_secret` – The PaymentIntent or SetupIntent client secret.
+///
+/// # Returns
+///
+/// The updated [`Intent`].
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects or returns `{ error }`.
+///
+pub async fn handle_next_action(
+    stripe: &JsStripe,
+    client_secret: &str,
+) -> Result<Intent, StripeError> {
+    let opts = to_value(&HandleNextActionOptions { client_secret })
+        .map_err(serde_error_to_stripe_error)?;
+    let promise = stripe
+        .handle_next_action(opts)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    let is_setup = Reflect::has(&result, &JsValue::from_str("setupIntent")).unwrap_or(false);
+    if is_setup {
+        intent_from_result(&result, "setupIntent").map(Intent::Setup)
+    } else {
+        intent_from_result(&result, "paymentIntent").map(Intent::Payment)
+    }
+}
+
+/// Fetch the current state of a PaymentIntent.
+///
+/// Useful on page load to check whether a payment already completed.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects, returns `{ error }`, or the
+/// response cannot be parsed.
+///
+pub async fn retrieve_payment_intent(
+    stripe: &JsStripe,
+    client_secret: &str,
+) -> Result<PaymentIntent, StripeError> {
+    let promise = stripe
+        .retrieve_payment_intent(client_secret)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    intent_from_result(&result, "paymentIntent")
+}
+
+/// Fetch the current state of a SetupIntent.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects, returns `{ error }`, or the
+/// response cannot be parsed.
+///
+pub async fn retrieve_setup_intent(
+    stripe: &JsStripe,
+    client_secret: &str,
+) -> Result<SetupIntent, StripeError> {
+    let promise = stripe
+        .retrieve_setup_intent(client_secret)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    intent_from_result(&result, "setupIntent")
+}
+
+/// Smallest [`PollOptions::interval_ms`] accepted, to stay clear of Stripe's
+/// rate limits.
+pub const MIN_POLL_INTERVAL_MS: u32 = 500;
+
+/// Polling schedule for [`wait_for_terminal_status`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PollOptions {
+    /// Delay before the second retrieval, in milliseconds; at least
+    /// [`MIN_POLL_INTERVAL_MS`].
+    pub interval_ms: u32,
+    /// Factor applied to the delay after each retrieval (`1.0` = fixed
+    /// interval); at least `1.0`.
+    pub backoff: f64,
+    /// Upper bound for the delay between retrievals, in milliseconds; at
+    /// least `interval_ms`.
+    pub max_interval_ms: u32,
+    /// Give up after this many milliseconds and return the last intent seen.
+    pub timeout_ms: u32,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            interval_ms: 1_000,
+            backoff: 1.5,
+            max_interval_ms: 10_000,
+            timeout_ms: 120_000,
+        }
+    }
+}
+
+impl PollOptions {
+    /// Check the schedule before polling.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `validation_error` [`StripeError`] if `interval_ms` is below
+    /// [`MIN_POLL_INTERVAL_MS`], `backoff` is below `1.0` (or NaN), or
+    /// `max_interval_ms` is below `interval_ms`.
+    pub fn validate(&self) -> Result<(), StripeError> {
+        let problem = if self.interval_ms < MIN_POLL_INTERVAL_MS {
+            format!("`interval_ms` must be at least {MIN_POLL_INTERVAL_MS}")
+        } else if self.backoff.is_nan() || self.backoff < 1.0 {
+            "`backoff` must be at least 1.0".to_string()
+        } else if self.max_interval_ms < self.interval_ms {
+            "`max_interval_ms` must be at least `interval_ms`".to_string()
+        } else {
+            return Ok(());
+        };
+        Err(StripeError {
+            message: problem,
+            error_type: Some("validation_error".into()),
+            code: Some("invalid_poll_options".into()),
+        })
+    }
+
+    /// Delays between retrievals, in milliseconds, before the timeout applies.
+    fn delays(&self) -> impl Iterator<Item = f64> {
+        let max_delay = f64::from(self.max_interval_ms);
+        let backoff = self.backoff;
+        std::iter::successors(Some(f64::from(self.interval_ms).min(max_delay)), move |d| {
+            Some((d * backoff).min(max_delay))
+        })
+    }
+}
+
+/// Poll a PaymentIntent until it leaves `processing`.
+///
+/// Bank debits (ACH, SEPA) and some wallets confirm asynchronously, so the
+/// intent sits in `processing` after `confirmPayment` resolves. This retrieves
+/// it on a browser timer, backing off per `options`.
+///
+/// # Returns
+///
+/// The first intent whose status is not `processing`, or the latest one
+/// (still `processing`) once `options.timeout_ms` has elapsed.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if `options` fail [`PollOptions::validate`] or
+/// any retrieval fails.
+///
+pub async fn wait_for_terminal_status(
+    stripe: &JsStripe,
+    client_secret: &str,
+    options: &PollOptions,
+) -> Result<PaymentIntent, StripeError> {
+    options.validate()?;
+    let started = Date::now();
+    let timeout = f64::from(options.timeout_ms);
+    let mut delays = options.delays();
+    loop {
+        let intent = retrieve_payment_intent(stripe, client_secret).await?;
+        let remaining = timeout - (Date::now() - started);
+        if intent.status != PaymentIntentStatus::Processing || remaining <= 0.0 {
+            return Ok(intent);
+        }
+        let delay = delays.next().unwrap_or(remaining);
+        sleep(delay.min(remaining) as i32).await?;
+    }
+}
+
+/// Resolve after `ms` milliseconds using `window.setTimeout`.
+async fn sleep(ms: i32) -> Result<(), StripeError> {
+    let window = web_sys::window().ok_or_else(|| StripeError {
+        message: "no window available for timers".into(),
+        error_type: None,
+        code: None,
+    })?;
+    let mut schedule_error = None;
+    let promise = Promise::new(&mut |resolve, _reject| {
+        if let Err(e) = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms) {
+            schedule_error = Some(e);
+        }
+    });
+    if let Some(e) = schedule_error {
+        return Err(js_to_stripe_error(e));
+    }
+    JsFuture::from(promise)
+        .await
+        .map(|_| ())
+        .map_err(js_to_stripe_error)
+}
+
+/// Read `result[key]` from a Stripe.js `{ <key> } | { error }` response.
+fn intent_from_result<T: DeserializeOwned>(result: &JsValue, key: &str) -> Result<T, StripeError> {
+    let error = Reflect::get(result, &JsValue::from_str("error")).unwrap_or(JsValue::UNDEFINED);
+    if !error.is_undefined() && !error.is_null() {
+        return Err(js_to_stripe_error(error));
+    }
+    let intent = Reflect::get(result, &JsValue::from_str(key)).map_err(js_to_stripe_error)?;
+    from_value(intent).map_err(serde_error_to_stripe_error)
+}
+
+/// `params` for `collectBankAccountForPayment`/`collectBankAccountForSetup`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CollectBankAccountParams {
+    /// Always `"us_bank_account"` today.
+    pub payment_method_type: String,
+    /// Billing details; `name` is required, `email` recommended.
+    pub payment_method_data: PaymentMethodData,
+}
+
+impl CollectBankAccountParams {
+    /// Params for a US bank account held by the customer described in `billing_details`.
+    pub fn us_bank_account(billing_details: BillingDetails) -> Self {
+        Self {
+            payment_method_type: "us_bank_account".into(),
+            payment_method_data: PaymentMethodData {
+                billing_details: Some(billing_details),
+            },
+        }
+    }
+}
+
+/// Options for `collectBankAccountFor{Payment,Setup}`.
+#[derive(Serialize)]
+struct CollectBankAccountOptions<'a> {
+    #[serde(rename = "clientSecret")]
+    client_secret: &'a str,
+    params: &'a CollectBankAccountParams,
+}
+
+/// How the customer proves they own the account.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MicrodepositVerification {
+    /// The two microdeposit amounts, in cents.
+    Amounts([u32; 2]),
+    /// The 6-character code from the single microdeposit's statement descriptor.
+    DescriptorCode(String),
+}
+
+/// Collect US bank account details for a PaymentIntent.
+///
+/// Opens Stripe's bank-linking modal and attaches the account to the intent.
+/// Follow up with [`confirm_us_bank_account_payment`].
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects or returns `{ error }`.
+///
+pub async fn collect_bank_account_for_payment(
+    stripe: &JsStripe,
+    client_secret: &str,
+    params: &CollectBankAccountParams,
+) -> Result<PaymentIntent, StripeError> {
+    let opts = to_value(&CollectBankAccountOptions {
+        client_secret,
+        params,
+    })
+    .map_err(serde_error_to_stripe_error)?;
+    let promise = stripe
+        .collect_bank_account_for_payment(opts)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    intent_from_result(&result, "paymentIntent")
+}
+
+/// Collect US bank account details for a SetupIntent.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects or returns `{ error }`.
+///
+pub async fn collect_bank_account_for_setup(
+    stripe: &JsStripe,
+    client_secret: &str,
+    params: &CollectBankAccountParams,
+) -> Result<SetupIntent, StripeError> {
+    let opts = to_value(&CollectBankAccountOptions {
+        client_secret,
+        params,
+    })
+    .map_err(serde_error_to_stripe_error)?;
+    let promise = stripe
+        .collect_bank_account_for_setup(opts)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    intent_from_result(&result, "setupIntent")
+}
+
+/// Confirm a PaymentIntent after [`collect_bank_account_for_payment`].
+///
+/// The returned intent is usually `processing`, or `requires_action` with
+/// [`NextAction::VerifyWithMicrodeposits`] when instant verification was not possible.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects or returns `{ error }`.
+///
+pub async fn confirm_us_bank_account_payment(
+    stripe: &JsStripe,
+    client_secret: &str,
+) -> Result<PaymentIntent, StripeError> {
+    let promise = stripe
+        .confirm_us_bank_account_payment(client_secret)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    intent_from_result(&result, "paymentIntent")
+}
+
+/// Verify a bank account using the microdeposits Stripe sent to it.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if the amounts/code are wrong, Stripe.js
+/// rejects, or returns `{ error }`.
+///
+pub async fn verify_microdeposits_for_payment(
+    stripe: &JsStripe,
+    client_secret: &str,
+    verification: &MicrodepositVerification,
+) -> Result<PaymentIntent, StripeError> {
+    let data = to_value(verification).map_err(serde_error_to_stripe_error)?;
+    let promise = stripe
+        .verify_microdeposits_for_payment(client_secret, data)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    intent_from_result(&result, "paymentIntent")
+}
+
+/// A Financial Connections Session returned once the customer closes the modal.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct FinancialConnectionsSession {
+    /// Unique identifier (`fcsess_…`).
+    pub id: String,
+    /// Accounts the customer linked; empty if they closed the modal early.
+    #[serde(default, deserialize_with = "deserialize_accounts")]
+    pub accounts: Vec<FinancialConnectionsAccount>,
+    /// Whether the session was created in live mode.
+    #[serde(default)]
+    pub livemode: bool,
+}
+
+/// A bank account linked through Financial Connections.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct FinancialConnectionsAccount {
+    /// Unique identifier (`fca_…`).
+    pub id: String,
+    /// Name of the bank, e.g. `"StripeBank"`.
+    #[serde(default)]
+    pub institution_name: Option<String>,
+    /// Customer-facing account name, e.g. `"Checking"`.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Last four digits of the account number.
+    #[serde(default)]
+    pub last4: Option<String>,
+    /// `"cash"`, `"credit"`, `"investment"` or `"other"`.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// e.g. `"checking"`, `"savings"`, `"credit_card"`.
+    #[serde(default)]
+    pub subcategory: Option<String>,
+    /// Data the customer agreed to share: `"balances"`, `"ownership"`,
+    /// `"payment_method"`, `"transactions"`.
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    /// `"active"`, `"inactive"` or `"disconnected"`.
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// Stripe.js returns `accounts` as an array; the REST API wraps it in a list object.
+fn deserialize_accounts<'de, D>(
+    deserializer: D,
+) -> Result<Vec<FinancialConnectionsAccount>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Accounts {
+        Array(Vec<FinancialConnectionsAccount>),
+        List {
+            data: Vec<FinancialConnectionsAccount>,
+        },
+    }
+    Ok(match Accounts::deserialize(deserializer)? {
+        Accounts::Array(data) | Accounts::List { data } => data,
+    })
+}
+
+/// Options for `collectFinancialConnectionsAccounts`.
+#[derive(Serialize)]
+struct FinancialConnectionsOptions<'a> {
+    #[serde(rename = "clientSecret")]
+    client_secret: &'a str,
+}
+
+/// Launch the Financial Connections modal and return the linked accounts.
+///
+/// `client_secret` belongs to a Financial Connections Session your server
+/// created with the permissions you need (e.g. `balances`, `ownership`).
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects or returns `{ error }`.
+///
+pub async fn collect_financial_connections_accounts(
+    stripe: &JsStripe,
+    client_secret: &str,
+) -> Result<FinancialConnectionsSession, StripeError> {
+    let opts = to_value(&FinancialConnectionsOptions { client_secret })
+        .map_err(serde_error_to_stripe_error)?;
+    let promise = stripe
+        .collect_financial_connections_accounts(opts)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    intent_from_result(&result, "financialConnectionsSession")
+}
+
+/// How the customer left the Stripe Identity modal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdentityVerificationOutcome {
+    /// The customer submitted their documents; listen for
+    /// `identity.verification_session.*` webhooks for the result.
+    Submitted,
+    /// The customer closed the modal before submitting.
+    Canceled,
+}
+
+/// Open the Stripe Identity modal for a VerificationSession.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects or returns an `{ error }`
+/// other than `session_cancelled`.
+///
+pub async fn verify_identity(
+    stripe: &JsStripe,
+    client_secret: &str,
+) -> Result<IdentityVerificationOutcome, StripeError> {
+    let promise = stripe
+        .verify_identity(client_secret)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    let error = Reflect::get(&result, &JsValue::from_str("error")).unwrap_or(JsValue::UNDEFINED);
+    if error.is_undefined() || error.is_null() {
+        return Ok(IdentityVerificationOutcome::Submitted);
+    }
+    let error = js_to_stripe_error(error);
+    match error.code.as_deref() {
+        Some("session_cancelled") => Ok(IdentityVerificationOutcome::Canceled),
+        _ => Err(error),
+    }
+}
+
+/// Async function returning a client secret, called by Stripe.js when it
+/// needs one (Checkout Sessions, Connect Account Sessions).
+///
+/// Compares by identity, so create it once (e.g. in `use_memo`) when passing
+/// it as a prop.
+#[derive(Clone)]
+pub struct FetchClientSecret(Rc<dyn Fn() -> SecretFuture>);
+
+type SecretFuture = Pin<Box<dyn Future<Output = Result<String, StripeError>>>>;
+
+impl FetchClientSecret {
+    /// Wrap an async closure, e.g. one that POSTs to your server.
+    ///
+    /// ```rust,ignore
+    /// let fetch = FetchClientSecret::new(|| async {
+    ///     let res = Request::post("/create-checkout-session").send().await.map_err(to_stripe_error)?;
+    ///     res.text().await.map_err(to_stripe_error)
+    /// });
+    /// ```
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<String, StripeError>> + 'static,
+    {
+        Self(Rc::new(move || Box::pin(f())))
+    }
+
+    /// A JS function returning a Promise of the secret, for Stripe.js options.
+    fn to_js(&self) -> Closure<dyn FnMut() -> Promise> {
+        self.to_js_while(Rc::new(Cell::new(true)))
+    }
+
+    /// Like [`to_js`](Self::to_js), but the Promise rejects once `active` is
+    /// cleared, for closures that must outlive their owner.
+    fn to_js_while(&self, active: Rc<Cell<bool>>) -> Closure<dyn FnMut() -> Promise> {
+        let fetch = self.0.clone();
+        Closure::wrap(Box::new(move || {
+            if !active.get() {
+                return Promise::reject(&JsValue::from_str("session ended"));
+            }
+            let fut = fetch();
+            future_to_promise(async move {
+                fut.await
+                    .map(JsValue::from)
+                    .map_err(|e| JsValue::from_str(&e.message))
+            })
+        }) as Box<dyn FnMut() -> Promise>)
+    }
+}
+
+impl PartialEq for FetchClientSecret {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Where Embedded Checkout gets its Checkout Session client secret.
+#[derive(Clone, PartialEq)]
+pub enum CheckoutSessionSecret {
+    /// A client secret you already have.
+    Known(String),
+    /// Called by Stripe.js when it needs the secret, e.g. to create the
+    /// Checkout Session on your server lazily.
+    Fetch(FetchClientSecret),
+}
+
+impl CheckoutSessionSecret {
+    /// Shorthand for `CheckoutSessionSecret::Fetch(FetchClientSecret::new(f))`.
+    pub fn fetch<F, Fut>(f: F) -> Self
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<String, StripeError>> + 'static,
+    {
+        Self::Fetch(FetchClientSecret::new(f))
+    }
+}
+
+impl From<String> for CheckoutSessionSecret {
+    fn from(client_secret: String) -> Self {
+        Self::Known(client_secret)
+    }
+}
+
+/// Set `clientSecret` or `fetchClientSecret` on `opts`, returning the
+/// closure that must outlive the Stripe.js instance.
+fn set_session_secret(
+    opts: &Object,
+    secret: CheckoutSessionSecret,
+) -> Result<Option<Closure<dyn FnMut() -> Promise>>, StripeError> {
+    match secret {
+        CheckoutSessionSecret::Known(client_secret) => {
+            Reflect::set(opts, &"clientSecret".into(), &client_secret.into())
+                .map_err(js_to_stripe_error)?;
+            Ok(None)
+        }
+        CheckoutSessionSecret::Fetch(fetch) => {
+            let closure = fetch.to_js();
+            Reflect::set(opts, &"fetchClientSecret".into(), closure.as_ref())
+                .map_err(js_to_stripe_error)?;
+            Ok(Some(closure))
+        }
+    }
+}
+
+/// A live Embedded Checkout instance.
+///
+/// Keeps the Rust callbacks handed to Stripe.js alive; dropping it calls
+/// `destroy()` so another instance can be created.
+pub struct EmbeddedCheckout {
+    inner: JsEmbeddedCheckout,
+    _fetch_client_secret: Option<Closure<dyn FnMut() -> Promise>>,
+    _on_complete: Option<Closure<dyn FnMut()>>,
+}
+
+impl EmbeddedCheckout {
+    /// Attach to the element matching `selector`, e.g. `"#checkout"`.
+    pub fn mount(&self, selector: &str) -> Result<(), StripeError> {
+        self.inner.mount(selector).map_err(js_to_stripe_error)
+    }
+
+    /// Attach to `element`.
+    pub fn mount_element(&self, element: &Element) -> Result<(), StripeError> {
+        self.inner
+            .mount_element(element)
+            .map_err(js_to_stripe_error)
+    }
+
+    /// Detach from the DOM; the instance can be mounted again.
+    pub fn unmount(&self) -> Result<(), StripeError> {
+        self.inner.unmount().map_err(js_to_stripe_error)
+    }
+
+    /// Remove Embedded Checkout from the page. Same as dropping it.
+    pub fn destroy(self) {}
+
+    /// The underlying Stripe.js instance.
+    pub fn js(&self) -> &JsEmbeddedCheckout {
+        &self.inner
+    }
+}
+
+impl Drop for EmbeddedCheckout {
+    fn drop(&mut self) {
+        let _ = self.inner.destroy();
+    }
+}
+
+/// Create an Embedded Checkout instance for a Checkout Session.
+///
+/// Stripe.js allows one instance per page; drop the previous
+/// [`EmbeddedCheckout`] before calling this again.
+///
+/// # Arguments
+///
+/// * `stripe` – The Stripe.js instance.
+/// * `secret` – The Checkout Session client secret, or an async function returning it.
+/// * `on_complete` – Called when the customer completes payment, instead of
+///   redirecting to the session's `return_url` (requires `redirect_on_completion: "if_required"`).
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects the options.
+///
+pub async fn init_embedded_checkout(
+    stripe: &JsStripe,
+    secret: CheckoutSessionSecret,
+    on_complete: Option<Box<dyn FnMut()>>,
+) -> Result<EmbeddedCheckout, StripeError> {
+    let opts = Object::new();
+    let fetch_client_secret = set_session_secret(&opts, secret)?;
+    let on_complete = on_complete.map(Closure::wrap);
+    if let Some(cb) = &on_complete {
+        Reflect::set(&opts, &"onComplete".into(), cb.as_ref()).map_err(js_to_stripe_error)?;
+    }
+    let promise = stripe
+        .init_embedded_checkout(opts.into())
+        .map_err(js_to_stripe_error)?;
+    let inner = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    Ok(EmbeddedCheckout {
+        inner: inner.unchecked_into(),
+        _fetch_client_secret: fetch_client_secret,
+        _on_complete: on_complete,
+    })
+}
+
+/// A monetary amount in a Custom Checkout session.
+///
+/// Recent Stripe.js versions send `{ amount: "$10.00", minorUnitsAmount: 1000 }`;
+/// older ones send the integer alone.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(from = "CheckoutAmountRepr")]
+pub struct CheckoutAmount {
+    /// Amount in the currency's smallest unit, e.g. cents.
+    pub minor_units: i64,
+    /// Localized, formatted amount, when Stripe.js provides one.
+    pub formatted: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CheckoutAmountRepr {
+    MinorUnits(i64),
+    Formatted {
+        #[serde(rename = "minorUnitsAmount")]
+        minor_units: i64,
+        amount: Option<String>,
+    },
+}
+
+impl From<CheckoutAmountRepr> for CheckoutAmount {
+    fn from(repr: CheckoutAmountRepr) -> Self {
+        match repr {
+            CheckoutAmountRepr::MinorUnits(minor_units) => Self {
+                minor_units,
+                formatted: None,
+            },
+            CheckoutAmountRepr::Formatted {
+                minor_units,
+                amount,
+            } => Self {
+                minor_units,
+                formatted: amount,
+            },
+        }
+    }
+}
+
+/// Checkout Session state exposed by Custom Checkout.
+///
+/// Delivered by `checkout.session()` and every `change` event.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutSession {
+    /// Unique identifier (`cs_…`), when exposed.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Three-letter ISO currency code, lowercase.
+    #[serde(default)]
+    pub currency: String,
+    /// Items being purchased.
+    #[serde(default)]
+    pub line_items: Vec<CheckoutLineItem>,
+    /// Subtotal, tax, shipping, discount and grand total.
+    #[serde(default)]
+    pub total: CheckoutTotals,
+    /// Applied discounts and promotion codes.
+    #[serde(default)]
+    pub discount_amounts: Option<Vec<CheckoutDiscount>>,
+    /// Shipping rates the customer can choose from.
+    #[serde(default)]
+    pub shipping_options: Vec<CheckoutShippingOption>,
+    /// The selected shipping rate.
+    #[serde(default)]
+    pub shipping: Option<CheckoutShipping>,
+    /// The shipping address set with `updateShippingAddress`.
+    #[serde(default)]
+    pub shipping_address: Option<CheckoutContact>,
+    /// The customer's email, if collected.
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Whether `confirm` can be called now.
+    #[serde(default)]
+    pub can_confirm: bool,
+    /// Open, expired or complete.
+    #[serde(default)]
+    pub status: CheckoutStatus,
+}
+
+/// One line item in a [`CheckoutSession`].
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutLineItem {
+    /// Line item id, passed to `updateLineItemQuantity`.
+    pub id: String,
+    /// Product name.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub quantity: u32,
+    /// Price of a single unit.
+    #[serde(default)]
+    pub unit_amount: Option<CheckoutAmount>,
+    /// Total for this line.
+    #[serde(default)]
+    pub total: Option<CheckoutAmount>,
+    /// Product image URLs.
+    #[serde(default)]
+    pub images: Vec<String>,
+}
+
+/// Totals of a [`CheckoutSession`].
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutTotals {
+    #[serde(default)]
+    pub subtotal: Option<CheckoutAmount>,
+    #[serde(default)]
+    pub tax_exclusive: Option<CheckoutAmount>,
+    #[serde(default)]
+    pub tax_inclusive: Option<CheckoutAmount>,
+    #[serde(default)]
+    pub shipping_rate: Option<CheckoutAmount>,
+    #[serde(default)]
+    pub discount: Option<CheckoutAmount>,
+    /// Amount the customer will be charged.
+    #[serde(default)]
+    pub total: Option<CheckoutAmount>,
+}
+
+/// A discount applied to a [`CheckoutSession`].
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutDiscount {
+    #[serde(default)]
+    pub amount: CheckoutAmount,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// The promotion code the customer entered, if any.
+    #[serde(default)]
+    pub promotion_code: Option<String>,
+}
+
+/// A shipping rate offered by a [`CheckoutSession`].
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutShippingOption {
+    /// Shipping rate id (`shr_…`).
+    pub id: String,
+    #[serde(default)]
+    pub amount: CheckoutAmount,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+/// The shipping rate selected on a [`CheckoutSession`].
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutShipping {
+    pub shipping_option: CheckoutShippingOption,
+}
+
+/// A name and address, as used by `updateShippingAddress` and `confirm`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct CheckoutContact {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub address: Address,
+}
+
+/// `CheckoutSession.status`.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CheckoutStatus {
+    #[default]
+    Open,
+    Expired,
+    Complete {
+        /// `"paid"`, `"unpaid"` or `"no_payment_required"`.
+        #[serde(default, rename = "paymentStatus")]
+        payment_status: Option<String>,
+    },
+    /// A status this crate does not know yet.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Appearance and loader options for Elements created by Custom Checkout.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct CheckoutElementsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appearance: Option<serde_json::Value>,
+    /// `"auto"`, `"always"` or `"never"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loader: Option<String>,
+}
+
+/// Parameters for [`CustomCheckout::confirm`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutConfirmParams {
+    /// Where to send the customer after redirect-based payment methods.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_url: Option<String>,
+    /// `"if_required"` to stay on the page when no redirect is needed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing_address: Option<CheckoutContact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping_address: Option<CheckoutContact>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LineItemQuantity<'a> {
+    line_item: &'a str,
+    quantity: u32,
+}
+
+/// A live Custom Checkout instance.
+///
+/// Tracks the latest [`CheckoutSession`] from `change` events and keeps the
+/// Rust callbacks handed to Stripe.js alive for as long as it exists.
+/// Dropping it unsubscribes the `change` handler and destroys the instance.
+pub struct CustomCheckout {
+    inner: JsCheckout,
+    session: Rc<RefCell<CheckoutSession>>,
+    active: Rc<Cell<bool>>,
+    _fetch_client_secret: Option<Closure<dyn FnMut() -> Promise>>,
+    on_change: Option<Closure<dyn FnMut(JsValue)>>,
+}
+
+impl Drop for CustomCheckout {
+    fn drop(&mut self) {
+        self.active.set(false);
+        if let Some(handler) = self.on_change.take() {
+            if self
+                .inner
+                .off("change", handler.as_ref().unchecked_ref())
+                .is_err()
+            {
+                // Without `off`, Stripe.js may still call the handler; keep
+                // it alive, inert, rather than invoke a dropped closure.
+                handler.forget();
+            }
+        }
+        let _ = self.inner.destroy();
+    }
+}
+
+impl CustomCheckout {
+    /// The latest session state.
+    pub fn session(&self) -> CheckoutSession {
+        self.session.borrow().clone()
+    }
+
+    /// Apply a promotion code, e.g. `"SUMMER10"`.
+    pub async fn apply_promotion_code(&self, code: &str) -> Result<CheckoutSession, StripeError> {
+        let promise = self
+            .inner
+            .apply_promotion_code(code)
+            .map_err(js_to_stripe_error)?;
+        self.settle(promise).await
+    }
+
+    /// Remove the applied promotion code.
+    pub async fn remove_promotion_code(&self) -> Result<CheckoutSession, StripeError> {
+        let promise = self
+            .inner
+            .remove_promotion_code()
+            .map_err(js_to_stripe_error)?;
+        self.settle(promise).await
+    }
+
+    /// Set the shipping address; Stripe recalculates tax and shipping.
+    pub async fn update_shipping_address(
+        &self,
+        address: &CheckoutContact,
+    ) -> Result<CheckoutSession, StripeError> {
+        let address = to_value(address).map_err(serde_error_to_stripe_error)?;
+        let promise = self
+            .inner
+            .update_shipping_address(address)
+            .map_err(js_to_stripe_error)?;
+        self.settle(promise).await
+    }
+
+    /// Change the quantity of the line item with id `line_item`.
+    pub async fn update_line_item_quantity(
+        &self,
+        line_item: &str,
+        quantity: u32,
+    ) -> Result<CheckoutSession, StripeError> {
+        let opts = to_value(&LineItemQuantity {
+            line_item,
+            quantity,
+        })
+        .map_err(serde_error_to_stripe_error)?;
+        let promise = self
+            .inner
+            .update_line_item_quantity(opts)
+            .map_err(js_to_stripe_error)?;
+        self.settle(promise).await
+    }
+
+    /// Confirm the session and collect payment with the mounted Payment Element.
+    ///
+    /// Redirect-based methods leave the page unless `redirect` is `"if_required"`.
+    pub async fn confirm(
+        &self,
+        params: &CheckoutConfirmParams,
+    ) -> Result<CheckoutSession, StripeError> {
+        let params = to_value(params).map_err(serde_error_to_stripe_error)?;
+        let promise = self.inner.confirm(params).map_err(js_to_stripe_error)?;
+        self.settle(promise).await
+    }
+
+    /// Create a Payment Element bound to this session; mount it yourself.
+    pub fn create_payment_element(
+        &self,
+        options: Option<&PaymentElementOptions>,
+    ) -> Result<JsPaymentElement, StripeError> {
+        let opts = match options {
+            Some(o) => to_value(o).map_err(serde_error_to_stripe_error)?,
+            None => JsValue::UNDEFINED,
+        };
+        self.inner
+            .create_payment_element(opts)
+            .map_err(js_to_stripe_error)
+    }
+
+    /// The underlying Stripe.js instance.
+    pub fn js(&self) -> &JsCheckout {
+        &self.inner
+    }
+
+    /// Await a `{ type, session } | { type, error }` action result and record the session.
+    async fn settle(&self, promise: Promise) -> Result<CheckoutSession, StripeError> {
+        let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+        let session: CheckoutSession = intent_from_result(&result, "session")?;
+        *self.session.borrow_mut() = session.clone();
+        Ok(session)
+    }
+}
+
+/// Create a Custom Checkout instance for a Checkout Session (`ui_mode: "custom"`).
+///
+/// # Arguments
+///
+/// * `stripe` – The Stripe.js instance.
+/// * `secret` – The Checkout Session client secret, or an async function returning it.
+/// * `elements_options` – Appearance for Elements created from the session.
+/// * `on_change` – Called with the new session whenever it changes.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects the options or the session
+/// cannot be loaded.
+///
+pub async fn init_checkout(
+    stripe: &JsStripe,
+    secret: CheckoutSessionSecret,
+    elements_options: Option<&CheckoutElementsOptions>,
+    mut on_change: Option<Box<dyn FnMut(CheckoutSession)>>,
+) -> Result<CustomCheckout, StripeError> {
+    let opts = Object::new();
+    let fetch_client_secret = set_session_secret(&opts, secret)?;
+    if let Some(eo) = elements_options {
+        let eo = to_value(eo).map_err(serde_error_to_stripe_error)?;
+        Reflect::set(&opts, &"elementsOptions".into(), &eo).map_err(js_to_stripe_error)?;
+    }
+    let value = stripe
+        .init_checkout(opts.into())
+        .map_err(js_to_stripe_error)?;
+    let inner: JsCheckout = JsFuture::from(Promise::resolve(&value))
+        .await
+        .map_err(js_to_stripe_error)?
+        .unchecked_into();
+    let initial = inner
+        .session()
+        .ok()
+        .and_then(|s| from_value::<CheckoutSession>(s).ok())
+        .unwrap_or_default();
+    let session = Rc::new(RefCell::new(initial));
+    let active = Rc::new(Cell::new(true));
+
+    let on_change_closure = {
+        let session = session.clone();
+        let active = active.clone();
+        Closure::wrap(Box::new(move |value: JsValue| {
+            if !active.get() {
+                return;
+            }
+            if let Ok(s) = from_value::<CheckoutSession>(value) {
+                *session.borrow_mut() = s.clone();
+                if let Some(cb) = on_change.as_mut() {
+                    cb(s);
+                }
+            }
+        }) as Box<dyn FnMut(JsValue)>)
+    };
+    inner.on(
+        "change",
+        on_change_closure.as_ref().unchecked_ref::<Function>(),
+    );
+
+    Ok(CustomCheckout {
+        inner,
+        session,
+        active,
+        _fetch_client_secret: fetch_client_secret,
+        on_change: Some(on_change_closure),
+    })
+}
+
+/// Appearance and locale for Connect embedded components.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ConnectOptions {
+    /// Connect appearance variables, e.g. `{ "variables": { "colorPrimary": "#635BFF" } }`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appearance: Option<serde_json::Value>,
+    /// UI locale, e.g. `"fr-FR"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+/// A Connect embedded component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectComponent {
+    /// `<stripe-connect-account-onboarding>`.
+    AccountOnboarding,
+    /// `<stripe-connect-payments>`.
+    Payments,
+    /// `<stripe-connect-payouts>`.
+    Payouts,
+    /// `<stripe-connect-account-management>`.
+    AccountManagement,
+}
+
+impl ConnectComponent {
+    /// The name passed to `stripeConnectInstance.create`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AccountOnboarding => "account-onboarding",
+            Self::Payments => "payments",
+            Self::Payouts => "payouts",
+            Self::AccountManagement => "account-management",
+        }
+    }
+}
+
+/// A live Connect.js instance.
+///
+/// Keeps the `fetchClientSecret` callback alive; Connect.js calls it again
+/// whenever the Account Session expires. Dropping it logs the session out.
+pub struct ConnectInstance {
+    inner: JsStripeConnect,
+    active: Rc<Cell<bool>>,
+    fetch_client_secret: Option<Closure<dyn FnMut() -> Promise>>,
+}
+
+impl Drop for ConnectInstance {
+    fn drop(&mut self) {
+        self.active.set(false);
+        let _ = self.inner.logout();
+        // Connect.js keeps `fetchClientSecret` for session refreshes and has
+        // no way to release it, so leave it alive; it rejects from now on.
+        if let Some(fetch) = self.fetch_client_secret.take() {
+            fetch.forget();
+        }
+    }
+}
+
+impl ConnectInstance {
+    /// Create the element for `component`; append it to the DOM yourself.
+    pub fn create(&self, component: ConnectComponent) -> Result<Element, StripeError> {
+        self.inner
+            .create(component.as_str())
+            .map_err(js_to_stripe_error)
+    }
+
+    /// Change appearance or locale of every component from this instance.
+    pub fn update(&self, options: &ConnectOptions) -> Result<(), StripeError> {
+        let opts = to_value(options).map_err(serde_error_to_stripe_error)?;
+        self.inner.update(opts).map_err(js_to_stripe_error)
+    }
+
+    /// End the Account Session.
+    pub async fn logout(&self) -> Result<(), StripeError> {
+        let promise = self.inner.logout().map_err(js_to_stripe_error)?;
+        JsFuture::from(promise)
+            .await
+            .map(|_| ())
+            .map_err(js_to_stripe_error)
+    }
+
+    /// The underlying Connect.js instance.
+    pub fn js(&self) -> &JsStripeConnect {
+        &self.inner
+    }
+}
+
+/// Initialize Connect.js for a connected account.
+///
+/// Connect.js must already be loaded (see [`use_connectjs`](crate::use_connectjs)).
+///
+/// # Arguments
+///
+/// * `publishable_key` – Your platform's publishable key (starts with `pk_`).
+/// * `fetch_client_secret` – Creates an Account Session on your server and
+///   returns its client secret.
+/// * `options` – Appearance and locale.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Connect.js is missing or rejects the params.
+///
+pub fn load_connect_and_initialize(
+    publishable_key: &str,
+    fetch_client_secret: &FetchClientSecret,
+    options: &ConnectOptions,
+) -> Result<ConnectInstance, StripeError> {
+    let params = to_value(options)
+        .map_err(serde_error_to_stripe_error)?
+        .dyn_into::<Object>()
+        .unwrap_or_default();
+    let active = Rc::new(Cell::new(true));
+    let fetch = fetch_client_secret.to_js_while(active.clone());
+    Reflect::set(&params, &"publishableKey".into(), &publishable_key.into())
+        .map_err(js_to_stripe_error)?;
+    Reflect::set(&params, &"fetchClientSecret".into(), fetch.as_ref())
+        .map_err(js_to_stripe_error)?;
+    let inner = init_connect(params.into()).map_err(js_to_stripe_error)?;
+    Ok(ConnectInstance {
+        inner,
+        active,
+        fetch_client_secret: Some(fetch),
+    })
+}
+
+/// Async function turning `(card_id, nonce)` into an ephemeral key secret,
+/// by asking your server to create an Issuing ephemeral key.
+///
+/// Compares by identity, so create it once when passing it as a prop.
+#[derive(Clone)]
+pub struct FetchEphemeralKey(Rc<dyn Fn(String, String) -> SecretFuture>);
+
+impl FetchEphemeralKey {
+    /// Wrap an async closure.
+    ///
+    /// ```rust,ignore
+    /// let fetch = FetchEphemeralKey::new(|card_id, nonce| async move {
+    ///     create_ephemeral_key(&card_id, &nonce).await
+    /// });
+    /// ```
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: Fn(String, String) -> Fut + 'static,
+        Fut: Future<Output = Result<String, StripeError>> + 'static,
+    {
+        Self(Rc::new(move |card, nonce| Box::pin(f(card, nonce))))
+    }
+
+    /// Fetch the ephemeral key secret for `card_id` and `nonce`.
+    pub async fn call(&self, card_id: &str, nonce: &str) -> Result<String, StripeError> {
+        (self.0)(card_id.to_string(), nonce.to_string()).await
+    }
+}
+
+impl PartialEq for FetchEphemeralKey {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EphemeralKeyNonceOptions<'a> {
+    issuing_card: &'a str,
+}
+
+/// Create a nonce for an Issuing card's ephemeral key.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects or returns `{ error }`.
+///
+pub async fn create_ephemeral_key_nonce(
+    stripe: &JsStripe,
+    card_id: &str,
+) -> Result<String, StripeError> {
+    let opts = to_value(&EphemeralKeyNonceOptions {
+        issuing_card: card_id,
+    })
+    .map_err(serde_error_to_stripe_error)?;
+    let promise = stripe
+        .create_ephemeral_key_nonce(opts)
+        .map_err(js_to_stripe_error)?;
+    let result = JsFuture::from(promise).await.map_err(js_to_stripe_error)?;
+    intent_from_result(&result, "nonce")
+}
+
+/// An Issuing card display element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IssuingElement {
+    /// `issuingCardNumberDisplay`.
+    CardNumber,
+    /// `issuingCardCvcDisplay`.
+    Cvc,
+    /// `issuingCardExpiryDisplay`.
+    Expiry,
+    /// `issuingCardPinDisplay`.
+    Pin,
+    /// `issuingCardCopyButton`; set `to_copy` in the options.
+    CopyButton,
+}
+
+impl IssuingElement {
+    /// The element type passed to `elements.create`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CardNumber => "issuingCardNumberDisplay",
+            Self::Cvc => "issuingCardCvcDisplay",
+            Self::Expiry => "issuingCardExpiryDisplay",
+            Self::Pin => "issuingCardPinDisplay",
+            Self::CopyButton => "issuingCardCopyButton",
+        }
+    }
+}
+
+/// Options for [`create_issuing_element`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuingElementOptions {
+    /// The Issuing card id (`ic_…`); required for display elements.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuing_card: Option<String>,
+    /// The nonce from [`create_ephemeral_key_nonce`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// The ephemeral key secret your server created with that nonce.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ephemeral_key_secret: Option<String>,
+    /// Copy buttons only: `"number"`, `"cvc"`, `"expiry"` or `"pin"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_copy: Option<String>,
+    /// Element style, e.g. `{ "base": { "fontSize": "16px" } }`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<serde_json::Value>,
+}
+
+/// Create an Issuing display element or copy button; mount it yourself.
+///
+/// `elements` can come from `stripe.elements()` with no client secret.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects the options.
+///
+pub fn create_issuing_element(
+    elements: &JsElements,
+    kind: IssuingElement,
+    options: &IssuingElementOptions,
+) -> Result<JsPaymentElement, StripeError> {
+    let opts = to_value(options).map_err(serde_error_to_stripe_error)?;
+    elements
+        .create_element(kind.as_str(), opts)
+        .map_err(js_to_stripe_error)
+}
+
+/// Create an Elements group on `stripe` to add Payment, Address and Link
+/// Authentication elements to.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects the options.
+///
+pub fn create_elements(
+    stripe: &JsStripe,
+    options: &ElementsOptions,
+) -> Result<JsElements, StripeError> {
+    let opts = to_value(options).map_err(serde_error_to_stripe_error)?;
+    stripe.elements(opts).map_err(js_to_stripe_error)
+}
+
+/// Create (but don't mount) a Payment Element in `elements`.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects the options.
+///
+pub fn create_payment_element(
+    elements: &JsElements,
+    options: Option<&PaymentElementOptions>,
+) -> Result<JsPaymentElement, StripeError> {
+    let opts = match options {
+        Some(o) => to_value(o).map_err(serde_error_to_stripe_error)?,
+        None => JsValue::undefined(),
+    };
+    elements
+        .create_element("payment", opts)
+        .map_err(js_to_stripe_error)
+}
+
+/// Which address the Address Element collects.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressMode {
+    /// A shipping address; also fills `shipping` when confirming.
+    #[default]
+    Shipping,
+    /// A billing address for the payment method.
+    Billing,
+}
+
+/// Prefilled values for the Address Element.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct AddressDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+}
+
+/// Options for the Address Element.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressElementOptions {
+    pub mode: AddressMode,
+    /// Two-letter country codes to offer; empty for all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_countries: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_values: Option<AddressDefaults>,
+}
+
+/// Create (but don't mount) an Address Element in `elements`.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects the options.
+///
+pub fn create_address_element(
+    elements: &JsElements,
+    options: &AddressElementOptions,
+) -> Result<JsPaymentElement, StripeError> {
+    let opts = to_value(options).map_err(serde_error_to_stripe_error)?;
+    elements
+        .create_element("address", opts)
+        .map_err(js_to_stripe_error)
+}
+
+/// Prefilled values for the Link Authentication Element.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct LinkAuthenticationDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+/// Options for the Link Authentication Element.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkAuthenticationElementOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_values: Option<LinkAuthenticationDefaults>,
+}
+
+/// Create (but don't mount) a Link Authentication Element in `elements`.
+///
+/// It collects the customer's email and lets returning Link customers
+/// autofill their saved details.
+///
+/// # Errors
+///
+/// Returns `Err(StripeError)` if Stripe.js rejects the options.
+///
+pub fn create_link_authentication_element(
+    elements: &JsElements,
+    options: &LinkAuthenticationElementOptions,
+) -> Result<JsPaymentElement, StripeError> {
+    let opts = to_value(options).map_err(serde_error_to_stripe_error)?;
+    elements
+        .create_element("linkAuthentication", opts)
+        .map_err(js_to_stripe_error)
+}
+
+/// Query params Stripe appends to `return_url` when a redirect-based payment
+/// method (iDEAL, Bancontact, Klarna, …) sends the shopper back.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RedirectReturn {
+    /// The PaymentIntent id, e.g. `pi_1Fxxxxxx`.
+    pub payment_intent: String,
+    /// The PaymentIntent client secret, used to retrieve the intent.
+    pub payment_intent_client_secret: String,
+    /// What Stripe reported for the redirect. Always confirm by retrieving the intent.
+    pub redirect_status: RedirectStatus,
+}
+
+/// The `redirect_status` query param.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RedirectStatus {
+    Succeeded,
+    Processing,
+    Failed,
+    /// Any value not listed above.
+    Other(String),
+}
+
+impl From<&str> for RedirectStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "succeeded" => Self::Succeeded,
+            "processing" => Self::Processing,
+            "failed" => Self::Failed,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// Parse the params Stripe appends to `return_url` after a redirect.
+///
+/// Accepts a full URL (`https://shop.example/done?payment_intent=…`) or just
+/// its query string. Returns `None` unless `payment_intent`,
+/// `payment_intent_client_secret` and `redirect_status` are all present.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// if let Some(ret) = parse_return_url(&window.location().href()?) {
+///     let outcome = retrieve(&ret.payment_intent_client_secret).await;
+/// }
+/// ```
+pub fn parse_return_url(url: &str) -> Option<RedirectReturn> {
+    let query = url.split_once('?').map_or(url, |(_, q)| q);
+    let query = query.split_once('#').map_or(query, |(q, _)| q);
+    let params = UrlSearchParams::new_with_str(query).ok()?;
+    Some(RedirectReturn {
+        payment_intent: params.get("payment_intent")?,
+        payment_intent_client_secret: params.get("payment_intent_client_secret")?,
+        redirect_status: params.get("redirect_status")?.as_str().into(),
+    })
+}
+
+/// [`parse_return_url`] applied to the current `window.location`.
+pub fn current_return_params() -> Option<RedirectReturn> {
+    let href = web_sys::window()?.location().href().ok()?;
+    parse_return_url(&href)
+}
+
+/// Convert any caught `JsValue` into a `StripeError` with best effort.
+pub(crate) fn js_to_stripe_error(value: JsValue) -> StripeError {
+    from_value::<StripeError>(value.clone()).unwrap_or_else(|_| StripeError {
+        message: value.as_string().unwrap_or_else(|| format!("{:?}", value)),
+        error_type: None,
+        code: None,
+    })
+}
+
+/// Serialize options for Stripe.js.
+///
+/// Maps become plain objects rather than JS `Map`s, so `#[serde(flatten)]`
+/// structs and `serde_json::Value` settings reach Stripe.js as ordinary
+/// properties.
+fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&Serializer::new().serialize_maps_as_objects(true))
 }
 
 /// Convert a `serde_wasm_bindgen::Error` (from `to_value`) into `StripeError`.
@@ -373,17 +2766,69 @@ impl StripeClient {
         }
     }
 
+    /// Create a new Stripe client with `locale`, `apiVersion`, `stripeAccount`
+    /// or `betas` set.
+    ///
+    /// # Arguments
+    ///
+    /// * `publishable_key` – Your Stripe publishable key (starts with `pk_`).
+    /// * `options` – Initialization options for `Stripe(publishableKey, options)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(StripeError)` if Stripe.js rejects the options.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let stripe = StripeClient::with_options(
+    ///     "pk_test_...",
+    ///     &StripeOptions { stripe_account: Some("acct_123".into()), ..Default::default() },
+    /// )?;
+    /// ```
+    pub fn with_options(
+        publishable_key: &str,
+        options: &StripeOptions,
+    ) -> Result<Self, StripeError> {
+        Ok(Self {
+            inner: create_stripe(publishable_key, options)?,
+        })
+    }
+
+    /// Create a new Stripe client, with `options` if given.
+    ///
+    /// Shorthand for [`new`](Self::new) or [`with_options`](Self::with_options),
+    /// for components taking an optional `stripe_options` prop.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(StripeError)` if Stripe.js rejects the options.
+    pub fn from_options(
+        publishable_key: &str,
+        options: Option<&StripeOptions>,
+    ) -> Result<Self, StripeError> {
+        match options {
+            Some(o) => Self::with_options(publishable_key, o),
+            None => Ok(Self::new(publishable_key)),
+        }
+    }
+
+    /// The underlying Stripe.js instance, for use with the free functions in this module.
+    pub fn js(&self) -> &JsStripe {
+        &self.inner
+    }
+
     /// Mount a Payment Element into the DOM.
     ///
     /// This does:
     /// 1. `stripe.elements({ clientSecret, appearance })`
     /// 2. `elements.create("payment", pe_options)`
-    /// 3. `paymentElement.mount(mount_id)`
+    /// 3. `paymentElement.mount(target)`
     ///
     /// # Arguments
     ///
     /// * `opts` – Configuration including the PaymentIntent client secret.
-    /// * `mount_id` – CSS selector or element ID (e.g. `"#payment-element"`).
+    /// * `target` – CSS selector (e.g. `"#payment-element"`) or a DOM element.
     /// * `pe_opts` – Optional layout/customization for the Payment Element.
     ///
     /// # Returns
@@ -397,7 +2842,7 @@ impl StripeClient {
     pub async fn mount_element(
         &self,
         opts: ElementsOptions,
-        mount_id: &str,
+        target: impl Into<MountTarget>,
         pe_opts: Option<PaymentElementOptions>,
     ) -> Result<(JsElements, JsPaymentElement), StripeError> {
         // Serialize Rust options into JsValue
@@ -406,14 +2851,15 @@ impl StripeClient {
         let elements = self.inner.elements(js_opts).map_err(js_to_stripe_error)?;
 
         // Build and mount the Payment Element
-        let pe_js = pe_opts
-            .map(|o| to_value(&o).unwrap())
-            .unwrap_or(JsValue::undefined());
+        let pe_js = match pe_opts {
+            Some(o) => to_value(&o).map_err(serde_error_to_stripe_error)?,
+            None => JsValue::undefined(),
+        };
 
         let payment_el = elements
             .create_element("payment", pe_js)
             .map_err(js_to_stripe_error)?;
-        payment_el.mount(mount_id).map_err(js_to_stripe_error)?;
+        target.into().mount(&payment_el)?;
 
         Ok((elements, payment_el))
     }
@@ -442,7 +2888,7 @@ impl StripeClient {
     /// # Arguments
     ///
     /// * `elements` – The `JsElements` from `mount_element`.
-    /// * `params` – Redirect URLs, save-card flags, etc.
+    /// * `params` – Redirect URLs, billing details, shipping, etc.
     /// * `client_secret` – `Some(...)` for two-step flows; `None` if you passed `clientSecret` earlier.
     /// * `redirect_if_required` – `true` to use `"if_required"` redirect behavior.
     ///
@@ -482,6 +2928,8 @@ impl StripeClient {
 
     /// Manually trigger an off-session 3DS/SCA challenge.
     ///
+    /// Legacy: prefer [`StripeClient::handle_next_action`], which returns the updated intent.
+    ///
     /// # Arguments
     ///
     /// * `client_secret` – The PaymentIntent’s client secret for off-session flows.
@@ -499,4 +2947,853 @@ impl StripeClient {
             .map(|_| ())
             .map_err(js_to_stripe_error)
     }
+
+    /// Fetch the current state of a PaymentIntent.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The PaymentIntent's client secret.
+    ///
+    /// # Returns
+    ///
+    /// The typed [`PaymentIntent`], or `Err(StripeError)` on failure.
+    pub async fn retrieve_payment_intent(
+        &self,
+        client_secret: &str,
+    ) -> Result<PaymentIntent, StripeError> {
+        retrieve_payment_intent(&self.inner, client_secret).await
+    }
+
+    /// Fetch the current state of a SetupIntent.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The SetupIntent's client secret.
+    ///
+    /// # Returns
+    ///
+    /// The typed [`SetupIntent`], or `Err(StripeError)` on failure.
+    pub async fn retrieve_setup_intent(
+        &self,
+        client_secret: &str,
+    ) -> Result<SetupIntent, StripeError> {
+        retrieve_setup_intent(&self.inner, client_secret).await
+    }
+
+    /// Poll a PaymentIntent until it leaves `processing`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The PaymentIntent's client secret.
+    /// * `options` – Interval, backoff and timeout.
+    ///
+    /// # Returns
+    ///
+    /// The settled [`PaymentIntent`] (or the last one seen on timeout), or
+    /// `Err(StripeError)` if a retrieval fails.
+    pub async fn wait_for_terminal_status(
+        &self,
+        client_secret: &str,
+        options: &PollOptions,
+    ) -> Result<PaymentIntent, StripeError> {
+        wait_for_terminal_status(&self.inner, client_secret, options).await
+    }
+
+    /// Handle the `next_action` of an intent your backend confirmed.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The PaymentIntent or SetupIntent client secret.
+    ///
+    /// # Returns
+    ///
+    /// The updated [`Intent`], or `Err(StripeError)` on failure.
+    pub async fn handle_next_action(&self, client_secret: &str) -> Result<Intent, StripeError> {
+        handle_next_action(&self.inner, client_secret).await
+    }
+
+    /// Collect US bank account details for a PaymentIntent.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The PaymentIntent's client secret.
+    /// * `params` – Usually [`CollectBankAccountParams::us_bank_account`].
+    ///
+    /// # Returns
+    ///
+    /// The updated [`PaymentIntent`], or `Err(StripeError)` on failure.
+    pub async fn collect_bank_account_for_payment(
+        &self,
+        client_secret: &str,
+        params: &CollectBankAccountParams,
+    ) -> Result<PaymentIntent, StripeError> {
+        collect_bank_account_for_payment(&self.inner, client_secret, params).await
+    }
+
+    /// Collect US bank account details for a SetupIntent.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The SetupIntent's client secret.
+    /// * `params` – Usually [`CollectBankAccountParams::us_bank_account`].
+    ///
+    /// # Returns
+    ///
+    /// The updated [`SetupIntent`], or `Err(StripeError)` on failure.
+    pub async fn collect_bank_account_for_setup(
+        &self,
+        client_secret: &str,
+        params: &CollectBankAccountParams,
+    ) -> Result<SetupIntent, StripeError> {
+        collect_bank_account_for_setup(&self.inner, client_secret, params).await
+    }
+
+    /// Confirm a PaymentIntent after collecting its bank account.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The PaymentIntent's client secret.
+    ///
+    /// # Returns
+    ///
+    /// The updated [`PaymentIntent`], or `Err(StripeError)` on failure.
+    pub async fn confirm_us_bank_account_payment(
+        &self,
+        client_secret: &str,
+    ) -> Result<PaymentIntent, StripeError> {
+        confirm_us_bank_account_payment(&self.inner, client_secret).await
+    }
+
+    /// Verify a bank account using its microdeposits.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The PaymentIntent's client secret.
+    /// * `verification` – The two amounts or the descriptor code.
+    ///
+    /// # Returns
+    ///
+    /// The updated [`PaymentIntent`], or `Err(StripeError)` on failure.
+    pub async fn verify_microdeposits_for_payment(
+        &self,
+        client_secret: &str,
+        verification: &MicrodepositVerification,
+    ) -> Result<PaymentIntent, StripeError> {
+        verify_microdeposits_for_payment(&self.inner, client_secret, verification).await
+    }
+
+    /// Launch the Financial Connections modal.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The Financial Connections Session's client secret.
+    ///
+    /// # Returns
+    ///
+    /// The [`FinancialConnectionsSession`] with the linked accounts, or `Err(StripeError)`.
+    pub async fn collect_financial_connections_accounts(
+        &self,
+        client_secret: &str,
+    ) -> Result<FinancialConnectionsSession, StripeError> {
+        collect_financial_connections_accounts(&self.inner, client_secret).await
+    }
+
+    /// Open the Stripe Identity verification modal.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_secret` – The VerificationSession's client secret.
+    ///
+    /// # Returns
+    ///
+    /// Whether the customer submitted or canceled, or `Err(StripeError)`.
+    pub async fn verify_identity(
+        &self,
+        client_secret: &str,
+    ) -> Result<IdentityVerificationOutcome, StripeError> {
+        verify_identity(&self.inner, client_secret).await
+    }
+
+    /// Create an Embedded Checkout instance for a Checkout Session.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret` – The Checkout Session client secret, or an async function returning it.
+    /// * `on_complete` – Called when the customer completes payment.
+    ///
+    /// # Returns
+    ///
+    /// The [`EmbeddedCheckout`] to mount, or `Err(StripeError)` on failure.
+    pub async fn init_embedded_checkout(
+        &self,
+        secret: CheckoutSessionSecret,
+        on_complete: Option<Box<dyn FnMut()>>,
+    ) -> Result<EmbeddedCheckout, StripeError> {
+        init_embedded_checkout(&self.inner, secret, on_complete).await
+    }
+
+    /// Create a Custom Checkout instance for a Checkout Session.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret` – The Checkout Session client secret, or an async function returning it.
+    /// * `elements_options` – Appearance for Elements created from the session.
+    /// * `on_change` – Called with the new session whenever it changes.
+    ///
+    /// # Returns
+    ///
+    /// The [`CustomCheckout`], or `Err(StripeError)` on failure.
+    pub async fn init_checkout(
+        &self,
+        secret: CheckoutSessionSecret,
+        elements_options: Option<&CheckoutElementsOptions>,
+        on_change: Option<Box<dyn FnMut(CheckoutSession)>>,
+    ) -> Result<CustomCheckout, StripeError> {
+        init_checkout(&self.inner, secret, elements_options, on_change).await
+    }
+
+    /// Create a Payment Method Messaging Element; no client secret needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` – Amount, currency, payment method types and country.
+    ///
+    /// # Returns
+    ///
+    /// The unmounted element, or `Err(StripeError)` on failure.
+    pub fn create_payment_method_messaging_element(
+        &self,
+        options: &PaymentMethodMessagingOptions,
+    ) -> Result<JsPaymentElement, StripeError> {
+        create_payment_method_messaging_element(&self.inner, options)
+    }
+
+    /// Create a nonce for an Issuing card's ephemeral key.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_id` – The Issuing card id (`ic_…`).
+    ///
+    /// # Returns
+    ///
+    /// The nonce, or `Err(StripeError)` on failure.
+    pub async fn create_ephemeral_key_nonce(&self, card_id: &str) -> Result<String, StripeError> {
+        create_ephemeral_key_nonce(&self.inner, card_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hide_billing(fields: BillingDetailsFields) -> PaymentElementOptions {
+        PaymentElementOptions {
+            fields: Some(PaymentElementFields {
+                billing_details: Some(fields),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn with_billing(billing_details: BillingDetails) -> ConfirmPaymentParams {
+        ConfirmPaymentParams {
+            payment_method_data: Some(PaymentMethodData {
+                billing_details: Some(billing_details),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn full_address() -> Address {
+        Address {
+            line1: Some("1 Main St".into()),
+            line2: Some(String::new()),
+            city: Some("Springfield".into()),
+            state: Some(String::new()),
+            postal_code: Some("12345".into()),
+            country: Some("US".into()),
+        }
+    }
+
+    #[test]
+    fn validate_without_hidden_fields() {
+        let params = ConfirmPaymentParams::default();
+        assert_eq!(params.validate(None), Ok(()));
+        assert_eq!(params.validate(Some(&Default::default())), Ok(()));
+        let auto = hide_billing(BillingDetailsFields::All(FieldCollection::Auto));
+        assert_eq!(params.validate(Some(&auto)), Ok(()));
+    }
+
+    #[test]
+    fn validate_all_never_requires_payment_method_data() {
+        let pe = hide_billing(BillingDetailsFields::All(FieldCollection::Never));
+
+        let err = ConfirmPaymentParams::default()
+            .validate(Some(&pe))
+            .unwrap_err();
+        assert_eq!(err.error_type.as_deref(), Some("validation_error"));
+        assert_eq!(err.code.as_deref(), Some("billing_details_required"));
+        assert!(err.message.contains("billing_details.name"));
+
+        let params = with_billing(BillingDetails {
+            name: Some("Jenny Rosen".into()),
+            email: Some("jenny@example.com".into()),
+            phone: Some("+15555550100".into()),
+            address: Some(full_address()),
+        });
+        assert_eq!(params.validate(Some(&pe)), Ok(()));
+
+        let params = with_billing(BillingDetails {
+            address: None,
+            ..params.payment_method_data.unwrap().billing_details.unwrap()
+        });
+        let err = params.validate(Some(&pe)).unwrap_err();
+        assert!(err.message.contains("billing_details.address.line1"));
+    }
+
+    #[test]
+    fn validate_each_checks_only_hidden_fields() {
+        let pe = hide_billing(BillingDetailsFields::Each {
+            name: Some(FieldCollection::Auto),
+            email: Some(FieldCollection::Never),
+            phone: None,
+            address: None,
+        });
+
+        let err = ConfirmPaymentParams::default()
+            .validate(Some(&pe))
+            .unwrap_err();
+        assert!(err.message.contains("billing_details.email"));
+
+        let params = with_billing(BillingDetails {
+            email: Some("jenny@example.com".into()),
+            ..Default::default()
+        });
+        assert_eq!(params.validate(Some(&pe)), Ok(()));
+    }
+
+    #[test]
+    fn validate_each_address_line() {
+        let pe = hide_billing(BillingDetailsFields::Each {
+            name: None,
+            email: None,
+            phone: None,
+            address: Some(AddressFields::Each {
+                line1: None,
+                line2: Some(FieldCollection::Never),
+                city: None,
+                state: None,
+                postal_code: Some(FieldCollection::Never),
+                country: None,
+            }),
+        });
+
+        let params = with_billing(BillingDetails {
+            address: Some(Address {
+                line2: Some(String::new()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let err = params.validate(Some(&pe)).unwrap_err();
+        assert!(err.message.contains("billing_details.address.postal_code"));
+
+        let params = with_billing(BillingDetails {
+            address: Some(Address {
+                line2: Some(String::new()),
+                postal_code: Some("12345".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert_eq!(params.validate(Some(&pe)), Ok(()));
+
+        let whole = hide_billing(BillingDetailsFields::Each {
+            name: None,
+            email: None,
+            phone: None,
+            address: Some(AddressFields::All(FieldCollection::Never)),
+        });
+        let err = params.validate(Some(&whole)).unwrap_err();
+        assert!(err.message.contains("billing_details.address.line1"));
+    }
+
+    #[test]
+    fn validate_rejects_extra_repeating_a_set_field() {
+        let mut params = ConfirmPaymentParams {
+            receipt_email: Some("jenny@example.com".into()),
+            extra: Some(json!({ "receipt_email": "other@example.com" })),
+            ..Default::default()
+        };
+        let err = params.validate(None).unwrap_err();
+        assert_eq!(err.code.as_deref(), Some("duplicate_param"));
+        assert!(err.message.contains("receipt_email"));
+
+        params.receipt_email = None;
+        assert_eq!(params.validate(None), Ok(()));
+    }
+
+    #[test]
+    fn payment_element_options_use_stripe_js_names() {
+        let options = PaymentElementOptions {
+            layout: Some("accordion".into()),
+            fields: Some(PaymentElementFields {
+                billing_details: Some(BillingDetailsFields::Each {
+                    name: Some(FieldCollection::Never),
+                    email: None,
+                    phone: Some(FieldCollection::Auto),
+                    address: Some(AddressFields::Each {
+                        line1: None,
+                        line2: None,
+                        city: None,
+                        state: None,
+                        postal_code: Some(FieldCollection::Never),
+                        country: Some(FieldCollection::Never),
+                    }),
+                }),
+            }),
+            extra: Some(json!({ "wallets": { "applePay": "never" } })),
+        };
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            json!({
+                "layout": "accordion",
+                "fields": {
+                    "billingDetails": {
+                        "name": "never",
+                        "phone": "auto",
+                        "address": { "postalCode": "never", "country": "never" },
+                    },
+                },
+                "wallets": { "applePay": "never" },
+            })
+        );
+
+        let all = hide_billing(BillingDetailsFields::All(FieldCollection::Never));
+        assert_eq!(
+            serde_json::to_value(&all).unwrap(),
+            json!({ "fields": { "billingDetails": "never" } })
+        );
+    }
+
+    #[test]
+    fn poll_delays_back_off_to_the_cap() {
+        let options = PollOptions::default();
+        let delays: Vec<f64> = options.delays().take(8).collect();
+        assert_eq!(
+            delays,
+            [1_000.0, 1_500.0, 2_250.0, 3_375.0, 5_062.5, 7_593.75, 10_000.0, 10_000.0]
+        );
+
+        let fixed = PollOptions {
+            interval_ms: 2_000,
+            backoff: 1.0,
+            max_interval_ms: 2_000,
+            ..Default::default()
+        };
+        assert!(fixed.delays().take(5).all(|d| d == 2_000.0));
+    }
+
+    #[test]
+    fn poll_options_reject_busy_loops() {
+        assert_eq!(PollOptions::default().validate(), Ok(()));
+        let invalid = [
+            PollOptions {
+                interval_ms: 0,
+                ..Default::default()
+            },
+            PollOptions {
+                interval_ms: MIN_POLL_INTERVAL_MS - 1,
+                ..Default::default()
+            },
+            PollOptions {
+                backoff: 0.0,
+                ..Default::default()
+            },
+            PollOptions {
+                backoff: 0.5,
+                ..Default::default()
+            },
+            PollOptions {
+                backoff: f64::NAN,
+                ..Default::default()
+            },
+            PollOptions {
+                interval_ms: 5_000,
+                max_interval_ms: 1_000,
+                ..Default::default()
+            },
+        ];
+        for options in invalid {
+            let err = options.validate().unwrap_err();
+            assert_eq!(
+                err.code.as_deref(),
+                Some("invalid_poll_options"),
+                "{options:?}"
+            );
+        }
+    }
+
+    fn next_action(value: serde_json::Value) -> NextAction {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn next_action_redirect_to_url() {
+        let action = next_action(json!({
+            "type": "redirect_to_url",
+            "redirect_to_url": {
+                "return_url": "https://example.com/return",
+                "url": "https://hooks.stripe.com/redirect/authenticate/src_1"
+            }
+        }));
+        assert_eq!(
+            action,
+            NextAction::RedirectToUrl(RedirectToUrl {
+                url: Some("https://hooks.stripe.com/redirect/authenticate/src_1".into()),
+                return_url: Some("https://example.com/return".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn next_action_oxxo() {
+        let action = next_action(json!({
+            "type": "display_oxxo_details",
+            "display_oxxo_details": {
+                "expires_after": 1_617_944_399,
+                "hosted_voucher_url": "https://payments.stripe.com/oxxo/voucher/test_1",
+                "number": "12345678901234657890123456789012"
+            }
+        }));
+        assert_eq!(
+            action,
+            NextAction::DisplayOxxoDetails(OxxoDisplayDetails {
+                expires_after: Some(1_617_944_399),
+                hosted_voucher_url: Some("https://payments.stripe.com/oxxo/voucher/test_1".into()),
+                number: Some("12345678901234657890123456789012".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn next_action_boleto() {
+        let action = next_action(json!({
+            "type": "boleto_display_details",
+            "boleto_display_details": {
+                "expires_at": 1_645_228_799,
+                "hosted_voucher_url": "https://payments.stripe.com/boleto/voucher/test_1",
+                "number": "01010101010101010101010101010101010101010101010",
+                "pdf": "https://payments.stripe.com/boleto/voucher/test_1/pdf"
+            }
+        }));
+        assert_eq!(
+            action,
+            NextAction::BoletoDisplayDetails(BoletoDisplayDetails {
+                expires_at: Some(1_645_228_799),
+                hosted_voucher_url: Some(
+                    "https://payments.stripe.com/boleto/voucher/test_1".into()
+                ),
+                number: Some("01010101010101010101010101010101010101010101010".into()),
+                pdf: Some("https://payments.stripe.com/boleto/voucher/test_1/pdf".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn next_action_konbini() {
+        let action = next_action(json!({
+            "type": "konbini_display_details",
+            "konbini_display_details": {
+                "expires_at": 1_642_431_599,
+                "hosted_voucher_url": "https://payments.stripe.com/konbini/voucher/test_1",
+                "stores": {
+                    "familymart": { "confirmation_number": "22222222222", "payment_code": "123456" },
+                    "lawson": { "confirmation_number": "22222222222", "payment_code": "123456" },
+                    "ministop": null,
+                    "seicomart": { "confirmation_number": "22222222222", "payment_code": "123456" }
+                }
+            }
+        }));
+        let store = KonbiniStore {
+            confirmation_number: Some("22222222222".into()),
+            payment_code: Some("123456".into()),
+        };
+        assert_eq!(
+            action,
+            NextAction::KonbiniDisplayDetails(KonbiniDisplayDetails {
+                expires_at: Some(1_642_431_599),
+                hosted_voucher_url: Some(
+                    "https://payments.stripe.com/konbini/voucher/test_1".into()
+                ),
+                stores: KonbiniStores {
+                    familymart: Some(store.clone()),
+                    lawson: Some(store.clone()),
+                    ministop: None,
+                    seicomart: Some(store),
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn next_action_qr_codes() {
+        let paynow = next_action(json!({
+            "type": "paynow_display_qr_code",
+            "paynow_display_qr_code": {
+                "data": "00020101021226550009SG.PAYNOW",
+                "hosted_instructions_url": "https://payments.stripe.com/qr/instructions/test_1",
+                "image_url_png": "https://qr.stripe.com/test_1.png",
+                "image_url_svg": "https://qr.stripe.com/test_1.svg"
+            }
+        }));
+        assert_eq!(
+            paynow,
+            NextAction::PaynowDisplayQrCode(QrCodeDetails {
+                data: Some("00020101021226550009SG.PAYNOW".into()),
+                hosted_instructions_url: Some(
+                    "https://payments.stripe.com/qr/instructions/test_1".into()
+                ),
+                image_data_url: None,
+                image_url_png: Some("https://qr.stripe.com/test_1.png".into()),
+                image_url_svg: Some("https://qr.stripe.com/test_1.svg".into()),
+            })
+        );
+
+        let promptpay = next_action(json!({
+            "type": "promptpay_display_qr_code",
+            "promptpay_display_qr_code": {
+                "data": "00020101021230820016A00000067701011",
+                "hosted_instructions_url": "https://payments.stripe.com/qr/instructions/test_2",
+                "image_url_png": "https://qr.stripe.com/test_2.png",
+                "image_url_svg": "https://qr.stripe.com/test_2.svg"
+            }
+        }));
+        let NextAction::PromptpayDisplayQrCode(details) = promptpay else {
+            panic!("expected PromptPay, got {promptpay:?}");
+        };
+        assert_eq!(
+            details.data.as_deref(),
+            Some("00020101021230820016A00000067701011")
+        );
+
+        let wechat = next_action(json!({
+            "type": "wechat_pay_display_qr_code",
+            "wechat_pay_display_qr_code": {
+                "data": "weixin://wxpay/bizpayurl?pr=test",
+                "hosted_instructions_url": "https://payments.stripe.com/qr/instructions/test_3",
+                "image_data_url": "data:image/png;base64,iVBORw0KGgo=",
+                "image_url_png": "https://qr.stripe.com/test_3.png",
+                "image_url_svg": "https://qr.stripe.com/test_3.svg"
+            }
+        }));
+        let NextAction::WechatPayDisplayQrCode(details) = wechat else {
+            panic!("expected WeChat Pay, got {wechat:?}");
+        };
+        assert_eq!(
+            details.image_data_url.as_deref(),
+            Some("data:image/png;base64,iVBORw0KGgo=")
+        );
+    }
+
+    #[test]
+    fn next_action_unknown_type_keeps_raw() {
+        let raw = json!({
+            "type": "use_stripe_sdk",
+            "use_stripe_sdk": { "type": "three_d_secure_redirect", "stripe_js": "https://hooks.stripe.com/3d" }
+        });
+        assert_eq!(
+            next_action(raw.clone()),
+            NextAction::Other {
+                kind: "use_stripe_sdk".into(),
+                raw,
+            }
+        );
+
+        let untyped = json!({ "alipay_handle_redirect": {} });
+        assert_eq!(
+            next_action(untyped.clone()),
+            NextAction::Other {
+                kind: String::new(),
+                raw: untyped,
+            }
+        );
+    }
+
+    #[test]
+    fn payment_intent_with_next_action() {
+        let intent: PaymentIntent = serde_json::from_value(json!({
+            "id": "pi_1",
+            "object": "payment_intent",
+            "amount": 1000,
+            "currency": "mxn",
+            "status": "requires_action",
+            "client_secret": "pi_1_secret_2",
+            "payment_method_types": ["oxxo"],
+            "next_action": {
+                "type": "display_oxxo_details",
+                "display_oxxo_details": { "number": "1234" }
+            },
+            "livemode": false
+        }))
+        .unwrap();
+        assert_eq!(intent.status, PaymentIntentStatus::RequiresAction);
+        assert_eq!(
+            intent.next_action,
+            Some(NextAction::DisplayOxxoDetails(OxxoDisplayDetails {
+                number: Some("1234".into()),
+                ..Default::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn checkout_amount_accepts_both_shapes() {
+        let formatted: CheckoutAmount =
+            serde_json::from_value(json!({ "amount": "$10.00", "minorUnitsAmount": 1000 }))
+                .unwrap();
+        assert_eq!(
+            formatted,
+            CheckoutAmount {
+                minor_units: 1000,
+                formatted: Some("$10.00".into()),
+            }
+        );
+
+        let bare: CheckoutAmount = serde_json::from_value(json!(1000)).unwrap();
+        assert_eq!(
+            bare,
+            CheckoutAmount {
+                minor_units: 1000,
+                formatted: None,
+            }
+        );
+
+        assert!(serde_json::from_value::<CheckoutAmount>(json!("$10.00")).is_err());
+    }
+
+    #[test]
+    fn checkout_status_variants() {
+        let status = |v| serde_json::from_value::<CheckoutStatus>(v).unwrap();
+        assert_eq!(status(json!({ "type": "open" })), CheckoutStatus::Open);
+        assert_eq!(
+            status(json!({ "type": "expired" })),
+            CheckoutStatus::Expired
+        );
+        assert_eq!(
+            status(json!({ "type": "complete", "paymentStatus": "paid" })),
+            CheckoutStatus::Complete {
+                payment_status: Some("paid".into()),
+            }
+        );
+        assert_eq!(
+            status(json!({ "type": "complete" })),
+            CheckoutStatus::Complete {
+                payment_status: None,
+            }
+        );
+        assert_eq!(
+            status(json!({ "type": "processing" })),
+            CheckoutStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn checkout_session_from_change_event() {
+        let session: CheckoutSession = serde_json::from_value(json!({
+            "id": "cs_test_1",
+            "currency": "usd",
+            "lineItems": [{
+                "id": "li_1",
+                "name": "T-shirt",
+                "quantity": 2,
+                "unitAmount": { "amount": "$20.00", "minorUnitsAmount": 2000 },
+                "total": { "amount": "$40.00", "minorUnitsAmount": 4000 },
+                "images": ["https://example.com/shirt.png"]
+            }],
+            "total": {
+                "subtotal": { "amount": "$40.00", "minorUnitsAmount": 4000 },
+                "discount": { "amount": "$4.00", "minorUnitsAmount": 400 },
+                "total": { "amount": "$36.00", "minorUnitsAmount": 3600 }
+            },
+            "discountAmounts": [{
+                "amount": { "amount": "$4.00", "minorUnitsAmount": 400 },
+                "displayName": "10% off",
+                "promotionCode": "SUMMER10"
+            }],
+            "shippingOptions": [{ "id": "shr_1", "amount": 0, "displayName": "Free" }],
+            "email": "jenny@example.com",
+            "canConfirm": true,
+            "status": { "type": "open" },
+            "someFutureField": { "ignored": true }
+        }))
+        .unwrap();
+        assert_eq!(session.id.as_deref(), Some("cs_test_1"));
+        assert_eq!(session.line_items[0].quantity, 2);
+        assert_eq!(
+            session.total.total.as_ref().map(|t| t.minor_units),
+            Some(3600)
+        );
+        assert_eq!(
+            session.discount_amounts.as_ref().unwrap()[0]
+                .promotion_code
+                .as_deref(),
+            Some("SUMMER10")
+        );
+        assert_eq!(session.shipping_options[0].amount.minor_units, 0);
+        assert!(session.can_confirm);
+        assert_eq!(session.status, CheckoutStatus::Open);
+
+        let minimal: CheckoutSession = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(minimal, CheckoutSession::default());
+    }
+
+    #[test]
+    fn confirm_params_use_stripe_js_names() {
+        let params = ConfirmPaymentParams {
+            return_url: Some("https://example.com/done".into()),
+            shipping: Some(ShippingDetails {
+                name: "Jenny Rosen".into(),
+                address: Address {
+                    line1: Some("1 Main St".into()),
+                    postal_code: Some("12345".into()),
+                    country: Some("US".into()),
+                    ..Default::default()
+                },
+                tracking_number: Some("TRK1".into()),
+                ..Default::default()
+            }),
+            payment_method_data: Some(PaymentMethodData {
+                billing_details: Some(BillingDetails {
+                    email: Some("jenny@example.com".into()),
+                    ..Default::default()
+                }),
+            }),
+            receipt_email: Some("jenny@example.com".into()),
+            expand: Some(vec!["payment_method".into()]),
+            setup_future_usage: Some(SetupFutureUsage::OffSession),
+            extra: Some(json!({ "save_payment_method": true })),
+        };
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            json!({
+                "return_url": "https://example.com/done",
+                "shipping": {
+                    "name": "Jenny Rosen",
+                    "address": { "line1": "1 Main St", "postal_code": "12345", "country": "US" },
+                    "tracking_number": "TRK1",
+                },
+                "payment_method_data": { "billing_details": { "email": "jenny@example.com" } },
+                "receipt_email": "jenny@example.com",
+                "expand": ["payment_method"],
+                "setup_future_usage": "off_session",
+                "save_payment_method": true,
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(ConfirmPaymentParams::default()).unwrap(),
+            json!({})
+        );
+    }
 }
@@ -0,0 +1,261 @@
+//! Yew components for Stripe Connect embedded components.
+//!
+//! Platforms can show connected accounts their onboarding, payments, payouts
+//! and account settings without leaving the app. [`StripeConnectProvider`]
+//! loads Connect.js and initializes one instance from an Account Session;
+//! the components below render inside it:
+//!
+//! - [`StripeConnectAccountOnboarding`]
+//! - [`StripeConnectPayments`]
+//! - [`StripeConnectPayouts`]
+//! - [`StripeConnectAccountManagement`]
+
+use crate::client::{
+    load_connect_and_initialize, ConnectComponent, ConnectInstance, ConnectOptions,
+    FetchClientSecret, StripeError,
+};
+use crate::use_connectjs;
+use std::rc::Rc;
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::js_sys::{Function, Reflect};
+use web_sys::Element;
+use yew::prelude::*;
+
+/// The Connect instance shared by [`StripeConnectProvider`]; `None` until
+/// Connect.js has loaded.
+///
+/// `on_error` forwards to the provider's latest `on_error` prop, so the
+/// components below report failures in the same place.
+#[derive(Clone, Default)]
+pub struct StripeConnectContext {
+    pub instance: Option<Rc<ConnectInstance>>,
+    pub on_error: Callback<StripeError>,
+}
+
+impl PartialEq for StripeConnectContext {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.instance, &other.instance) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Properties for the [`StripeConnectProvider`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your platform's Publishable Key (`pk_…`).
+/// * `fetch_client_secret` – Creates an Account Session on your server and
+///   returns its client secret. Create it once (e.g. with `use_memo`).
+/// * `options` – Appearance and locale; changes are applied in place.
+/// * `on_error` – Callback invoked with [`StripeError`] if initialization, an
+///   `options` update or creating one of the components fails.
+/// * `children` – Connect components to render.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeConnectProviderProps {
+    pub publishable_key: String,
+    pub fetch_client_secret: FetchClientSecret,
+    #[prop_or_default]
+    pub options: ConnectOptions,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Yew function component initializing Connect.js for its children.
+///
+/// A new key or fetcher logs the current session out and initializes a
+/// fresh instance.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let fetch = use_memo((), |_| FetchClientSecret::new(|| async {
+///     create_account_session().await
+/// }));
+/// html! {
+///     <StripeConnectProvider
+///         publishable_key="pk_test_123".to_string()
+///         fetch_client_secret={(*fetch).clone()}
+///     >
+///         <StripeConnectPayments />
+///         <StripeConnectPayouts />
+///     </StripeConnectProvider>
+/// }
+/// ```
+#[function_component(StripeConnectProvider)]
+pub fn stripe_connect_provider(props: &StripeConnectProviderProps) -> Html {
+    let connect_ready = use_connectjs();
+    let instance = use_state(|| None::<Rc<ConnectInstance>>);
+    let latest_on_error = use_mut_ref(Callback::default);
+    *latest_on_error.borrow_mut() = props.on_error.clone();
+    let report_error = {
+        let latest_on_error = latest_on_error.clone();
+        use_memo((), move |_| {
+            Callback::from(move |e: StripeError| latest_on_error.borrow().emit(e))
+        })
+    };
+
+    {
+        let instance = instance.clone();
+        let on_error = latest_on_error.clone();
+        let options = props.options.clone();
+        use_effect_with(
+            (
+                connect_ready,
+                props.publishable_key.clone(),
+                props.fetch_client_secret.clone(),
+            ),
+            move |(ready, publishable_key, fetch)| {
+                if *ready {
+                    match load_connect_and_initialize(publishable_key, fetch, &options) {
+                        Ok(connect) => instance.set(Some(Rc::new(connect))),
+                        Err(e) => on_error.borrow().emit(e),
+                    }
+                }
+                // Dropping the last handle logs the old session out.
+                move || instance.set(None)
+            },
+        );
+    }
+
+    {
+        let instance = instance.clone();
+        let on_error = latest_on_error.clone();
+        use_effect_with(props.options.clone(), move |options| {
+            if let Some(connect) = instance.as_ref() {
+                if let Err(e) = connect.update(options) {
+                    on_error.borrow().emit(e);
+                }
+            }
+            || ()
+        });
+    }
+
+    let context = StripeConnectContext {
+        instance: (*instance).clone(),
+        on_error: (*report_error).clone(),
+    };
+    html! {
+        <ContextProvider<StripeConnectContext> {context}>
+            { for props.children.iter() }
+        </ContextProvider<StripeConnectContext>>
+    }
+}
+
+/// Properties for the individual Connect components.
+///
+/// # Fields
+///
+/// * `class` – Classes for the container element.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeConnectComponentProps {
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// Properties for [`StripeConnectAccountOnboarding`].
+///
+/// # Fields
+///
+/// * `on_exit` – Callback invoked when the account leaves the onboarding flow.
+/// * `class` – Classes for the container element.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeConnectAccountOnboardingProps {
+    #[prop_or_default]
+    pub on_exit: Callback<()>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// `<stripe-connect-account-onboarding>`: collect the connected account's
+/// details and accept Stripe's terms.
+#[function_component(StripeConnectAccountOnboarding)]
+pub fn stripe_connect_account_onboarding(props: &StripeConnectAccountOnboardingProps) -> Html {
+    use_connect_component(
+        ConnectComponent::AccountOnboarding,
+        Some(props.on_exit.clone()),
+        props.class.clone(),
+    )
+}
+
+/// `<stripe-connect-payments>`: list, refund and dispute payments.
+#[function_component(StripeConnectPayments)]
+pub fn stripe_connect_payments(props: &StripeConnectComponentProps) -> Html {
+    use_connect_component(ConnectComponent::Payments, None, props.class.clone())
+}
+
+/// `<stripe-connect-payouts>`: balances and payout schedule.
+#[function_component(StripeConnectPayouts)]
+pub fn stripe_connect_payouts(props: &StripeConnectComponentProps) -> Html {
+    use_connect_component(ConnectComponent::Payouts, None, props.class.clone())
+}
+
+/// `<stripe-connect-account-management>`: update business and bank details.
+#[function_component(StripeConnectAccountManagement)]
+pub fn stripe_connect_account_management(props: &StripeConnectComponentProps) -> Html {
+    use_connect_component(
+        ConnectComponent::AccountManagement,
+        None,
+        props.class.clone(),
+    )
+}
+
+/// Create `component` from the provider's instance and attach it to a container.
+#[hook]
+fn use_connect_component(
+    component: ConnectComponent,
+    on_exit: Option<Callback<()>>,
+    class: Classes,
+) -> Html {
+    let context = use_context::<StripeConnectContext>().unwrap_or_default();
+    let container = use_node_ref();
+    let latest_on_exit = use_mut_ref(|| None::<Callback<()>>);
+    *latest_on_exit.borrow_mut() = on_exit;
+
+    {
+        let container = container.clone();
+        use_effect_with(context, move |context| {
+            let mut attached = None;
+            let mut exit_closure = None;
+            if let (Some(connect), Some(parent)) =
+                (context.instance.as_ref(), container.cast::<Element>())
+            {
+                match connect.create(component) {
+                    Ok(el) => {
+                        if latest_on_exit.borrow().is_some() {
+                            let closure = Closure::wrap(Box::new(move || {
+                                if let Some(on_exit) = latest_on_exit.borrow().as_ref() {
+                                    on_exit.emit(());
+                                }
+                            })
+                                as Box<dyn FnMut()>);
+                            if let Ok(setter) = Reflect::get(&el, &JsValue::from_str("setOnExit")) {
+                                if let Ok(setter) = setter.dyn_into::<Function>() {
+                                    let _ = setter.call1(&el, closure.as_ref());
+                                }
+                            }
+                            exit_closure = Some(closure);
+                        }
+                        if parent.append_child(&el).is_ok() {
+                            attached = Some(el);
+                        }
+                    }
+                    Err(e) => context.on_error.emit(e),
+                }
+            }
+            move || {
+                if let Some(el) = attached {
+                    el.remove();
+                }
+                drop(exit_closure);
+            }
+        });
+    }
+
+    html! { <div {class} ref={container}></div> }
+}
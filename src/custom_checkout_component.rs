@@ -0,0 +1,199 @@
+//! Yew context for Custom Checkout: your own UI on a Stripe Checkout Session.
+//!
+//! [`CustomCheckoutProvider`] initializes `stripe.initCheckout` once Stripe.js
+//! has loaded and re-renders its children whenever the session changes.
+//! Descendants read the session and call actions through
+//! [`use_custom_checkout`]:
+//!
+//! ```rust,ignore
+//! #[function_component(Summary)]
+//! fn summary() -> Html {
+//!     let Some(ctx) = use_custom_checkout() else { return html! {} };
+//!     let Some(session) = ctx.session else { return html! { "Loading…" } };
+//!     html! {
+//!         <ul>
+//!             { for session.line_items.iter().map(|li| html! {
+//!                 <li>{ format!("{} × {}", li.quantity, li.name) }</li>
+//!             }) }
+//!         </ul>
+//!     }
+//! }
+//! ```
+
+use crate::client::{
+    init_checkout, CheckoutElementsOptions, CheckoutSession, CheckoutSessionSecret, CustomCheckout,
+    StripeClient, StripeError, StripeOptions,
+};
+use crate::use_stripejs;
+use std::cell::Cell;
+use std::rc::Rc;
+use yew::functional::hook;
+use yew::prelude::*;
+
+/// Value provided by [`CustomCheckoutProvider`].
+///
+/// # Fields
+///
+/// * `checkout` – The live instance, once initialized; call
+///   `apply_promotion_code`, `update_shipping_address`,
+///   `update_line_item_quantity` or `confirm` on it.
+/// * `session` – The latest session state, updated on every `change` event.
+/// * `error` – Set if initialization failed.
+#[derive(Clone, Default)]
+pub struct CustomCheckoutContext {
+    pub checkout: Option<Rc<CustomCheckout>>,
+    pub session: Option<CheckoutSession>,
+    pub error: Option<StripeError>,
+}
+
+impl PartialEq for CustomCheckoutContext {
+    fn eq(&self, other: &Self) -> bool {
+        let same_checkout = match (&self.checkout, &other.checkout) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        same_checkout && self.session == other.session && self.error == other.error
+    }
+}
+
+/// Properties for the [`CustomCheckoutProvider`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `stripe_options` – `locale`, `stripeAccount`, etc. for the Stripe instance.
+/// * `secret` – The Checkout Session client secret, or an async function returning it.
+/// * `elements_options` – Appearance for Elements created from the session.
+/// * `on_error` – Callback invoked with [`StripeError`] if initialization fails.
+/// * `children` – Components that call [`use_custom_checkout`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct CustomCheckoutProviderProps {
+    pub publishable_key: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    pub secret: CheckoutSessionSecret,
+    #[prop_or_default]
+    pub elements_options: Option<CheckoutElementsOptions>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Yew function component providing a [`CustomCheckoutContext`].
+///
+/// A new `secret`, `publishable_key`, `stripe_options` or `elements_options`
+/// drops the current instance and initializes a fresh one.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// html! {
+///     <CustomCheckoutProvider
+///         publishable_key="pk_test_123".to_string()
+///         secret={CheckoutSessionSecret::Known(client_secret.clone())}
+///     >
+///         <Summary />
+///         <PromoCodeInput />
+///         <PayButton />
+///     </CustomCheckoutProvider>
+/// }
+/// ```
+#[function_component(CustomCheckoutProvider)]
+pub fn custom_checkout_provider(props: &CustomCheckoutProviderProps) -> Html {
+    let stripe_ready = use_stripejs();
+    let checkout = use_state(|| None::<Rc<CustomCheckout>>);
+    let session = use_state(|| None::<CheckoutSession>);
+    let error = use_state(|| None::<StripeError>);
+
+    {
+        let checkout = checkout.clone();
+        let session = session.clone();
+        let error = error.clone();
+        let on_error = props.on_error.clone();
+        use_effect_with(
+            (
+                stripe_ready,
+                props.publishable_key.clone(),
+                props.stripe_options.clone(),
+                props.secret.clone(),
+                props.elements_options.clone(),
+            ),
+            move |(ready, publishable_key, stripe_options, secret, elements_options)| {
+                let alive = Rc::new(Cell::new(true));
+                if *ready {
+                    let alive = alive.clone();
+                    let checkout = checkout.clone();
+                    let session = session.clone();
+                    let client =
+                        StripeClient::from_options(publishable_key, stripe_options.as_ref());
+                    let secret = secret.clone();
+                    let elements_options = elements_options.clone();
+                    error.set(None);
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let on_change = {
+                            let session = session.clone();
+                            let alive = alive.clone();
+                            Box::new(move |s: CheckoutSession| {
+                                if alive.get() {
+                                    session.set(Some(s));
+                                }
+                            })
+                        };
+                        let result = match client {
+                            Ok(client) => {
+                                init_checkout(
+                                    client.js(),
+                                    secret,
+                                    elements_options.as_ref(),
+                                    Some(on_change),
+                                )
+                                .await
+                            }
+                            Err(e) => Err(e),
+                        };
+                        if !alive.get() {
+                            return;
+                        }
+                        match result {
+                            Ok(instance) => {
+                                session.set(Some(instance.session()));
+                                checkout.set(Some(Rc::new(instance)));
+                            }
+                            Err(e) => {
+                                on_error.emit(e.clone());
+                                error.set(Some(e));
+                            }
+                        }
+                    });
+                }
+                move || {
+                    alive.set(false);
+                    session.set(None);
+                    checkout.set(None);
+                }
+            },
+        );
+    }
+
+    let context = CustomCheckoutContext {
+        checkout: (*checkout).clone(),
+        session: (*session).clone(),
+        error: (*error).clone(),
+    };
+
+    html! {
+        <ContextProvider<CustomCheckoutContext> {context}>
+            { for props.children.iter() }
+        </ContextProvider<CustomCheckoutContext>>
+    }
+}
+
+/// Custom hook: read the nearest [`CustomCheckoutProvider`]'s context.
+///
+/// Returns `None` outside a provider.
+#[hook]
+pub fn use_custom_checkout() -> Option<CustomCheckoutContext> {
+    use_context::<CustomCheckoutContext>()
+}
@@ -0,0 +1,320 @@
+//! Yew context for composing several Stripe Elements in one form.
+//!
+//! [`Elements`] creates the Stripe instance and an Elements group once
+//! Stripe.js has loaded. Element components rendered inside it register
+//! with that group, so `elements.submit()` and `confirm_payment()` see all
+//! of them:
+//!
+//! - [`PaymentElement`]
+//! - [`AddressElement`]
+//! - [`LinkAuthenticationElement`]
+//!
+//! Any descendant reaches the instances through [`use_stripe`] and
+//! [`use_elements`]:
+//!
+//! ```rust,ignore
+//! #[function_component(PayButton)]
+//! fn pay_button() -> Html {
+//!     let (Some(stripe), Some(elements)) = (use_stripe(), use_elements()) else {
+//!         return html! { <button disabled=true>{ "Pay" }</button> };
+//!     };
+//!     let onclick = Callback::from(move |_| {
+//!         let (stripe, elements) = (stripe.clone(), elements.clone());
+//!         spawn_local(async move {
+//!             if let Err(e) = validate_payment_element(&elements).await {
+//!                 return log::warn!("{}", e.message);
+//!             }
+//!             match confirm_payment(&stripe, &elements, Default::default(), None, true).await {
+//!                 PaymentResult::Success(intent) => log::info!("{} {}", intent.id, intent.status),
+//!                 PaymentResult::Error(e) => log::error!("Payment failed: {}", e.message),
+//!             }
+//!         });
+//!     });
+//!     html! { <button {onclick}>{ "Pay" }</button> }
+//! }
+//! ```
+
+use crate::client::{
+    create_address_element, create_elements, create_link_authentication_element,
+    create_payment_element, js_to_stripe_error, on_element_change, AddressElementOptions,
+    ElementChangeEvent, ElementsOptions, LinkAuthenticationElementOptions, PaymentElementOptions,
+    StripeClient, StripeError, StripeOptions,
+};
+use crate::{use_stripejs, JsElements, JsPaymentElement, JsStripe};
+use wasm_bindgen::JsValue;
+use web_sys::Element;
+use yew::functional::hook;
+use yew::prelude::*;
+
+/// Value provided by [`Elements`]; both fields are `None` until Stripe.js
+/// has loaded.
+#[derive(Clone, Default)]
+pub struct ElementsContext {
+    pub stripe: Option<JsStripe>,
+    pub elements: Option<JsElements>,
+}
+
+impl PartialEq for ElementsContext {
+    fn eq(&self, other: &Self) -> bool {
+        js_key(&self.stripe) == js_key(&other.stripe)
+            && js_key(&self.elements) == js_key(&other.elements)
+    }
+}
+
+/// Properties for the [`Elements`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `stripe_options` – `locale`, `stripeAccount`, etc. for the Stripe instance.
+/// * `options` – The client secret and appearance for the Elements group.
+/// * `on_error` – Callback invoked with [`StripeError`] if the group cannot be created.
+/// * `children` – Element components and anything calling [`use_stripe`]/[`use_elements`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct ElementsProps {
+    pub publishable_key: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    pub options: ElementsOptions,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Yew function component providing a Stripe instance and Elements group.
+///
+/// A new key, Stripe options or `options` creates a fresh group; the
+/// element components below remount into it. If that fails, the context is
+/// emptied until the props change again.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// html! {
+///     <Elements
+///         publishable_key="pk_test_123".to_string()
+///         options={ElementsOptions { client_secret: secret.clone(), appearance: None }}
+///     >
+///         <LinkAuthenticationElement />
+///         <AddressElement />
+///         <PaymentElement />
+///         <PayButton />
+///     </Elements>
+/// }
+/// ```
+#[function_component(Elements)]
+pub fn elements(props: &ElementsProps) -> Html {
+    let stripe_ready = use_stripejs();
+    let context = use_state(ElementsContext::default);
+
+    {
+        let context = context.clone();
+        let on_error = props.on_error.clone();
+        use_effect_with(
+            (
+                stripe_ready,
+                props.publishable_key.clone(),
+                props.stripe_options.clone(),
+                props.options.clone(),
+            ),
+            move |(ready, pk, stripe_opts, options)| {
+                if *ready {
+                    let client = StripeClient::from_options(pk, stripe_opts.as_ref());
+                    let created = client.and_then(|client| {
+                        let elements = create_elements(client.js(), options)?;
+                        Ok((client.js().clone(), elements))
+                    });
+                    match created {
+                        Ok((stripe, elements)) => context.set(ElementsContext {
+                            stripe: Some(stripe),
+                            elements: Some(elements),
+                        }),
+                        // Descendants must not confirm against the previous group.
+                        Err(e) => {
+                            context.set(ElementsContext::default());
+                            on_error.emit(e);
+                        }
+                    }
+                }
+                || ()
+            },
+        );
+    }
+
+    html! {
+        <ContextProvider<ElementsContext> context={(*context).clone()}>
+            { for props.children.iter() }
+        </ContextProvider<ElementsContext>>
+    }
+}
+
+/// Custom hook: the nearest [`Elements`]' Stripe instance.
+///
+/// Returns `None` outside a provider or before Stripe.js has loaded.
+#[hook]
+pub fn use_stripe() -> Option<JsStripe> {
+    use_context::<ElementsContext>().and_then(|c| c.stripe)
+}
+
+/// Custom hook: the nearest [`Elements`]' Elements group.
+///
+/// Returns `None` outside a provider or before Stripe.js has loaded.
+#[hook]
+pub fn use_elements() -> Option<JsElements> {
+    use_context::<ElementsContext>().and_then(|c| c.elements)
+}
+
+/// Properties for the [`PaymentElement`] component.
+///
+/// # Fields
+///
+/// * `options` – Layout and fields for the Payment Element.
+/// * `on_change` – Callback invoked with each [`ElementChangeEvent`].
+/// * `on_error` – Callback invoked with [`StripeError`] if the element cannot be created.
+/// * `class` – Classes for the container element.
+#[derive(Properties, PartialEq, Clone)]
+pub struct PaymentElementProps {
+    #[prop_or_default]
+    pub options: Option<PaymentElementOptions>,
+    #[prop_or_default]
+    pub on_change: Callback<ElementChangeEvent>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// The Payment Element, registered with the surrounding [`Elements`].
+#[function_component(PaymentElement)]
+pub fn payment_element(props: &PaymentElementProps) -> Html {
+    use_stripe_element(
+        props.options.clone(),
+        |elements, options| create_payment_element(elements, options.as_ref()),
+        props.on_change.clone(),
+        props.on_error.clone(),
+        props.class.clone(),
+    )
+}
+
+/// Properties for the [`AddressElement`] component.
+///
+/// # Fields
+///
+/// * `options` – Shipping or billing mode, allowed countries and defaults.
+/// * `on_change` – Callback invoked with each [`ElementChangeEvent`]; `value`
+///   holds `{ name, address, phone }`.
+/// * `on_error` – Callback invoked with [`StripeError`] if the element cannot be created.
+/// * `class` – Classes for the container element.
+#[derive(Properties, PartialEq, Clone)]
+pub struct AddressElementProps {
+    #[prop_or_default]
+    pub options: AddressElementOptions,
+    #[prop_or_default]
+    pub on_change: Callback<ElementChangeEvent>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// The Address Element, registered with the surrounding [`Elements`].
+#[function_component(AddressElement)]
+pub fn address_element(props: &AddressElementProps) -> Html {
+    use_stripe_element(
+        props.options.clone(),
+        create_address_element,
+        props.on_change.clone(),
+        props.on_error.clone(),
+        props.class.clone(),
+    )
+}
+
+/// Properties for the [`LinkAuthenticationElement`] component.
+///
+/// # Fields
+///
+/// * `options` – A default email.
+/// * `on_change` – Callback invoked with each [`ElementChangeEvent`]; `value`
+///   holds `{ email }`.
+/// * `on_error` – Callback invoked with [`StripeError`] if the element cannot be created.
+/// * `class` – Classes for the container element.
+#[derive(Properties, PartialEq, Clone)]
+pub struct LinkAuthenticationElementProps {
+    #[prop_or_default]
+    pub options: LinkAuthenticationElementOptions,
+    #[prop_or_default]
+    pub on_change: Callback<ElementChangeEvent>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// The Link Authentication Element, registered with the surrounding [`Elements`].
+#[function_component(LinkAuthenticationElement)]
+pub fn link_authentication_element(props: &LinkAuthenticationElementProps) -> Html {
+    use_stripe_element(
+        props.options.clone(),
+        create_link_authentication_element,
+        props.on_change.clone(),
+        props.on_error.clone(),
+        props.class.clone(),
+    )
+}
+
+/// Create an element in the provider's group with `create`, mount it into a
+/// container and forward its `change` events. Recreated when the group or
+/// `options` change; destroyed on unmount.
+#[hook]
+fn use_stripe_element<O>(
+    options: O,
+    create: fn(&JsElements, &O) -> Result<JsPaymentElement, StripeError>,
+    on_change: Callback<ElementChangeEvent>,
+    on_error: Callback<StripeError>,
+    class: Classes,
+) -> Html
+where
+    O: PartialEq + 'static,
+{
+    let elements = use_elements();
+    let container = use_node_ref();
+    // The listener lives as long as the element, so it reads the latest callback.
+    let latest_on_change = use_mut_ref(Callback::default);
+    *latest_on_change.borrow_mut() = on_change;
+
+    {
+        let container = container.clone();
+        let group = js_key(&elements);
+        use_effect_with((group, options), move |(_, options)| {
+            let mut mounted = None;
+            if let (Some(elements), Some(target)) = (&elements, container.cast::<Element>()) {
+                let created = create(elements, options).and_then(|el| {
+                    el.mount_element(&target).map_err(js_to_stripe_error)?;
+                    Ok(el)
+                });
+                match created {
+                    Ok(el) => {
+                        let listener =
+                            on_element_change(&el, move |ev| latest_on_change.borrow().emit(ev));
+                        mounted = Some((el, listener));
+                    }
+                    Err(e) => on_error.emit(e),
+                }
+            }
+            move || {
+                if let Some((el, listener)) = mounted {
+                    drop(listener);
+                    let _ = el.destroy();
+                }
+            }
+        });
+    }
+
+    html! { <div {class} ref={container}></div> }
+}
+
+/// Compare JS handles by identity, for contexts and effect dependencies.
+fn js_key<T: AsRef<JsValue>>(handle: &Option<T>) -> Option<JsValue> {
+    handle.as_ref().map(|h| h.as_ref().clone())
+}
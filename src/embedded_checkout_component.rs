@@ -0,0 +1,149 @@
+//! A Yew component embedding a Stripe Checkout Session in the page.
+//!
+//! Use this instead of [`StripeCheckout`](crate::StripeCheckout) when your
+//! server creates Checkout Sessions (`ui_mode: "embedded"`) rather than raw
+//! PaymentIntents. Stripe hosts the whole form — line items, tax, shipping,
+//! payment — inside an iframe mounted into this component.
+
+use crate::client::{
+    init_embedded_checkout, CheckoutSessionSecret, EmbeddedCheckout, StripeClient, StripeError,
+    StripeOptions,
+};
+use crate::use_stripejs;
+use std::cell::Cell;
+use std::rc::Rc;
+use web_sys::Element;
+use yew::prelude::*;
+
+/// Properties for the [`StripeEmbeddedCheckout`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `stripe_options` – `locale`, `stripeAccount`, etc. for the Stripe instance.
+/// * `secret` – The Checkout Session client secret, or an async function
+///   returning it (see [`CheckoutSessionSecret::fetch`]).
+/// * `on_complete` – Callback invoked when the customer completes payment.
+///   Only fires for sessions created with `redirect_on_completion: "if_required"`
+///   or `"never"`; otherwise Stripe redirects to the session's `return_url`.
+/// * `on_error` – Callback invoked with [`StripeError`] if initialization fails.
+/// * `class` – Classes for the container element.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeEmbeddedCheckoutProps {
+    pub publishable_key: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    pub secret: CheckoutSessionSecret,
+    #[prop_or_default]
+    pub on_complete: Callback<()>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// Yew function component for Stripe Embedded Checkout.
+///
+/// Initializes once Stripe.js has loaded and destroys the instance on
+/// unmount, so only one `StripeEmbeddedCheckout` should be rendered at a time.
+/// A new `secret`, `publishable_key` or `stripe_options` destroys the
+/// instance and initializes a fresh one.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let secret = use_memo((), |_| CheckoutSessionSecret::fetch(|| async {
+///     fetch_session_secret().await
+/// }));
+/// html! {
+///     <StripeEmbeddedCheckout
+///         publishable_key="pk_test_123".to_string()
+///         secret={(*secret).clone()}
+///         on_complete={Callback::from(|_| log::info!("Order complete"))}
+///     />
+/// }
+/// ```
+#[function_component(StripeEmbeddedCheckout)]
+pub fn stripe_embedded_checkout(props: &StripeEmbeddedCheckoutProps) -> Html {
+    let stripe_ready = use_stripejs();
+    let container = use_node_ref();
+    let checkout = use_mut_ref(|| None::<EmbeddedCheckout>);
+    let error = use_state(|| None::<String>);
+    // Stripe takes `onComplete` once at init, so it reads the latest callback.
+    let latest_on_complete = use_mut_ref(Callback::default);
+    *latest_on_complete.borrow_mut() = props.on_complete.clone();
+
+    {
+        let container = container.clone();
+        let checkout = checkout.clone();
+        let error = error.clone();
+        let on_error = props.on_error.clone();
+        use_effect_with(
+            (
+                stripe_ready,
+                props.publishable_key.clone(),
+                props.stripe_options.clone(),
+                props.secret.clone(),
+            ),
+            move |(ready, publishable_key, stripe_options, secret)| {
+                let alive = Rc::new(Cell::new(true));
+                if *ready {
+                    let alive = alive.clone();
+                    let checkout = checkout.clone();
+                    let client =
+                        StripeClient::from_options(publishable_key, stripe_options.as_ref());
+                    let secret = secret.clone();
+                    error.set(None);
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let result = match client {
+                            Ok(client) => {
+                                init_embedded_checkout(
+                                    client.js(),
+                                    secret,
+                                    Some(Box::new(move || latest_on_complete.borrow().emit(()))),
+                                )
+                                .await
+                            }
+                            Err(e) => Err(e),
+                        };
+                        // Unmounted while Stripe.js was initializing: the instance
+                        // is dropped (and destroyed) here.
+                        if !alive.get() {
+                            return;
+                        }
+                        let result = result.and_then(|instance| {
+                            if let Some(el) = container.cast::<Element>() {
+                                instance.mount_element(&el)?;
+                            }
+                            Ok(instance)
+                        });
+                        match result {
+                            Ok(instance) => *checkout.borrow_mut() = Some(instance),
+                            Err(e) => {
+                                on_error.emit(e.clone());
+                                error.set(Some(e.message));
+                            }
+                        }
+                    });
+                }
+                move || {
+                    alive.set(false);
+                    checkout.borrow_mut().take();
+                }
+            },
+        );
+    }
+
+    html! {
+        <div class={props.class.clone()}>
+            <div ref={container}></div>
+            {
+                if let Some(msg) = &*error {
+                    html!{ <div class="text-red-500 text-sm mt-2">{ msg }</div> }
+                } else {
+                    Html::default()
+                }
+            }
+        </div>
+    }
+}
@@ -0,0 +1,89 @@
+//! A Yew button that links bank accounts through Stripe Financial Connections.
+//!
+//! Your server creates a Financial Connections Session with the permissions
+//! it needs (`balances`, `ownership`, `transactions`, …) and passes its client
+//! secret down. [`StripeFinancialConnectionsButton`] opens Stripe's modal and
+//! emits the accounts the customer linked, read-only.
+
+use crate::client::{
+    collect_financial_connections_accounts, FinancialConnectionsAccount, StripeError, StripeOptions,
+};
+use crate::stripe_action::use_stripe_action;
+use yew::prelude::*;
+
+/// Properties for the [`StripeFinancialConnectionsButton`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `stripe_options` – `locale`, `stripeAccount`, etc. for the Stripe instance.
+/// * `client_secret` – The Financial Connections Session client secret.
+/// * `on_linked` – Callback invoked with the linked accounts; empty if the
+///   customer closed the modal without linking.
+/// * `on_error` – Callback invoked with [`StripeError`] on failure.
+/// * `button_label` – Override the button text (default: `"Link bank account"`).
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeFinancialConnectionsButtonProps {
+    pub publishable_key: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    pub client_secret: String,
+    #[prop_or_default]
+    pub on_linked: Callback<Vec<FinancialConnectionsAccount>>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub button_label: Option<String>,
+}
+
+/// Yew function component launching the Financial Connections modal.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// html! {
+///     <StripeFinancialConnectionsButton
+///         publishable_key="pk_test_123".to_string()
+///         client_secret={session_secret.clone()}
+///         on_linked={Callback::from(|accounts: Vec<FinancialConnectionsAccount>| {
+///             for a in accounts {
+///                 log::info!("{:?} ••{:?}", a.institution_name, a.last4);
+///             }
+///         })}
+///     />
+/// }
+/// ```
+#[function_component(StripeFinancialConnectionsButton)]
+pub fn stripe_financial_connections_button(props: &StripeFinancialConnectionsButtonProps) -> Html {
+    let action = use_stripe_action(
+        props.publishable_key.clone(),
+        props.stripe_options.clone(),
+        props.on_error.clone(),
+    );
+
+    let on_click = {
+        let action = action.clone();
+        let props = props.clone();
+        Callback::from(move |_: MouseEvent| {
+            let secret = props.client_secret.clone();
+            let on_linked = props.on_linked.clone();
+            action.run(
+                move |client| async move {
+                    collect_financial_connections_accounts(client.js(), &secret).await
+                },
+                move |session| on_linked.emit(session.accounts),
+            );
+        })
+    };
+
+    html! {
+        <div class="flex flex-col gap-2 w-full">
+            { action.button(
+                on_click,
+                props.button_label.clone().unwrap_or_else(|| "Link bank account".to_string()),
+                "Connecting…",
+            ) }
+            { action.error() }
+        </div>
+    }
+}
@@ -0,0 +1,86 @@
+//! A Yew button that runs a Stripe Identity verification.
+//!
+//! Your server creates a VerificationSession and passes its client secret
+//! down. [`StripeIdentityButton`] opens Stripe's document and selfie capture
+//! modal and reports whether the customer submitted or closed it. The
+//! verification result itself arrives through webhooks.
+
+use crate::client::{verify_identity, IdentityVerificationOutcome, StripeError, StripeOptions};
+use crate::stripe_action::use_stripe_action;
+use yew::prelude::*;
+
+/// Properties for the [`StripeIdentityButton`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `stripe_options` – `locale`, `stripeAccount`, etc. for the Stripe instance.
+/// * `client_secret` – The VerificationSession client secret.
+/// * `on_submitted` – Callback invoked once the customer submits.
+/// * `on_canceled` – Callback invoked if the customer closes the modal.
+/// * `on_error` – Callback invoked with [`StripeError`] on failure.
+/// * `button_label` – Override the button text (default: `"Verify your identity"`).
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeIdentityButtonProps {
+    pub publishable_key: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    pub client_secret: String,
+    #[prop_or_default]
+    pub on_submitted: Callback<()>,
+    #[prop_or_default]
+    pub on_canceled: Callback<()>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub button_label: Option<String>,
+}
+
+/// Yew function component launching the Stripe Identity modal.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// html! {
+///     <StripeIdentityButton
+///         publishable_key="pk_test_123".to_string()
+///         client_secret={verification_secret.clone()}
+///         on_submitted={Callback::from(|_| log::info!("Documents submitted"))}
+///     />
+/// }
+/// ```
+#[function_component(StripeIdentityButton)]
+pub fn stripe_identity_button(props: &StripeIdentityButtonProps) -> Html {
+    let action = use_stripe_action(
+        props.publishable_key.clone(),
+        props.stripe_options.clone(),
+        props.on_error.clone(),
+    );
+
+    let on_click = {
+        let action = action.clone();
+        let props = props.clone();
+        Callback::from(move |_: MouseEvent| {
+            let secret = props.client_secret.clone();
+            let props = props.clone();
+            action.run(
+                move |client| async move { verify_identity(client.js(), &secret).await },
+                move |outcome| match outcome {
+                    IdentityVerificationOutcome::Submitted => props.on_submitted.emit(()),
+                    IdentityVerificationOutcome::Canceled => props.on_canceled.emit(()),
+                },
+            );
+        })
+    };
+
+    html! {
+        <div class="flex flex-col gap-2 w-full">
+            { action.button(
+                on_click,
+                props.button_label.clone().unwrap_or_else(|| "Verify your identity".to_string()),
+                "Verifying…",
+            ) }
+            { action.error() }
+        </div>
+    }
+}
@@ -9,6 +9,17 @@
 //! script’s `load` event fires, then returns `true`
 //! on every subsequent call.
 //!
+//! `use_stripe_pricing_table_js()`, `use_stripe_buy_button_js()` and
+//! `use_connectjs()` load Stripe's other scripts the same way.
+//!
+//! A [`StripeJsConfig`] (via [`StripeJsProvider`] or [`set_stripejs_config`])
+//! changes the Stripe.js URL, adds a CSP `nonce` and `crossorigin` to every
+//! injected tag, opts out of advanced fraud signals and adds preconnect hints.
+//!
+//! Each script has one process-wide loader. Every hook instance and every
+//! `load_stripejs()` future subscribes to it, so whoever asks first injects
+//! the tag and everyone learns when it loads or fails.
+//!
 //! # Cargo.toml
 //! ```toml
 //! yew = "0.21"                          # Yew framework
@@ -35,75 +46,719 @@
 //! }
 //! ```
 
+use crate::client::StripeError;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
-use web_sys::js_sys::Reflect;
-use web_sys::HtmlScriptElement;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::{Function, Promise, Reflect};
+use web_sys::{Element, HtmlScriptElement};
 use yew::functional::hook;
 use yew::prelude::*;
 
 /// Custom hook: load Stripe.js v3 exactly once and track readiness.
 ///
 /// # Returns
-/// - `false` while the `<script>` is being fetched & parsed.
+/// - `false` while the `<script>` is being fetched & parsed, or if it failed.
 /// - `true` once `window.Stripe` exists (script loaded & parsed).
 ///
 /// All components using `use_stripejs()` will share the same script
-/// insertion logic and state.
+/// insertion logic and state. Use [`use_stripejs_status`] to tell a slow
+/// load from a blocked one.
 #[hook]
 pub fn use_stripejs() -> bool {
-    // Initialize state: check if `window.Stripe` already present
-    let loaded = use_state(|| {
-        web_sys::window()
-            .and_then(|win| {
-                Reflect::has(&win, &JsValue::from_str("Stripe"))
-                    .ok()
-                    .filter(|&b| b) // keep only `true`
-            })
-            .map(|_| true)
-            .unwrap_or(false)
+    use_stripejs_status(None).status.is_ready()
+}
+
+/// How long [`use_stripejs_status`] waits for Stripe.js by default.
+pub const STRIPEJS_TIMEOUT_MS: u32 = 15_000;
+
+/// Where Stripe.js is loaded from by default.
+pub const STRIPEJS_URL: &str = "https://js.stripe.com/v3/";
+
+/// Origins worth a `<link rel="preconnect">` before paying: the script
+/// host, the API and the fraud-signals frame.
+pub const STRIPE_PRECONNECT_ORIGINS: [&str; 3] = [
+    "https://js.stripe.com",
+    "https://api.stripe.com",
+    "https://m.stripe.network",
+];
+
+/// How the loader injects Stripe's scripts.
+///
+/// Provide it to a subtree with [`StripeJsProvider`], or call
+/// [`set_stripejs_config`] before anything loads to set it for the whole
+/// app (including [`load_stripejs`]). The first load wins: changing the
+/// config after the script has been injected has no effect.
+///
+/// # Fields
+///
+/// * `src` – The Stripe.js URL, e.g. a local stand-in for tests (default: [`STRIPEJS_URL`]).
+/// * `nonce` – CSP nonce set on every injected `<script>`.
+/// * `cross_origin` – `crossorigin` attribute for every injected `<script>`, e.g. `"anonymous"`.
+/// * `advanced_fraud_signals` – `false` appends `?advancedFraudSignals=false` to `src`
+///   (default: `true`).
+/// * `preconnect` – Origins to add `<link rel="preconnect">` hints for, e.g.
+///   [`STRIPE_PRECONNECT_ORIGINS`] (default: none).
+/// * `timeout_ms` – How long to wait before reporting a failure
+///   (default: [`STRIPEJS_TIMEOUT_MS`]).
+#[derive(Clone, Debug, PartialEq)]
+pub struct StripeJsConfig {
+    pub src: String,
+    pub nonce: Option<String>,
+    pub cross_origin: Option<String>,
+    pub advanced_fraud_signals: bool,
+    pub preconnect: Vec<String>,
+    pub timeout_ms: u32,
+}
+
+impl Default for StripeJsConfig {
+    fn default() -> Self {
+        Self {
+            src: STRIPEJS_URL.to_string(),
+            nonce: None,
+            cross_origin: None,
+            advanced_fraud_signals: true,
+            preconnect: Vec::new(),
+            timeout_ms: STRIPEJS_TIMEOUT_MS,
+        }
+    }
+}
+
+impl StripeJsConfig {
+    /// The Stripe.js URL to inject, with the fraud-signals opt-out applied.
+    pub fn script_url(&self) -> String {
+        if self.advanced_fraud_signals {
+            self.src.clone()
+        } else {
+            let separator = if self.src.contains('?') { '&' } else { '?' };
+            format!("{}{separator}advancedFraudSignals=false", self.src)
+        }
+    }
+}
+
+thread_local! {
+    static CONFIG: RefCell<StripeJsConfig> = RefCell::new(StripeJsConfig::default());
+}
+
+/// Set the [`StripeJsConfig`] used outside any [`StripeJsProvider`],
+/// like `loadStripe.setLoadParameters` in `@stripe/stripe-js`.
+///
+/// Call it before the first component or [`load_stripejs`] loads Stripe.js.
+pub fn set_stripejs_config(config: StripeJsConfig) {
+    CONFIG.with(|c| *c.borrow_mut() = config);
+}
+
+fn stripejs_config() -> StripeJsConfig {
+    CONFIG.with(|c| c.borrow().clone())
+}
+
+/// Properties for the [`StripeJsProvider`] component.
+///
+/// # Fields
+///
+/// * `config` – The [`StripeJsConfig`] for hooks below this provider.
+/// * `children` – Components that load Stripe's scripts.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeJsProviderProps {
+    pub config: StripeJsConfig,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Yew function component configuring how descendants load Stripe's scripts.
+///
+/// # Example
+/// ```rust,ignore
+/// let config = StripeJsConfig {
+///     nonce: Some(csp_nonce.clone()),
+///     advanced_fraud_signals: false,
+///     preconnect: STRIPE_PRECONNECT_ORIGINS.map(String::from).to_vec(),
+///     ..Default::default()
+/// };
+/// html! {
+///     <StripeJsProvider {config}>
+///         <App />
+///     </StripeJsProvider>
+/// }
+/// ```
+#[function_component(StripeJsProvider)]
+pub fn stripe_js_provider(props: &StripeJsProviderProps) -> Html {
+    html! {
+        <ContextProvider<StripeJsConfig> context={props.config.clone()}>
+            { for props.children.iter() }
+        </ContextProvider<StripeJsConfig>>
+    }
+}
+
+/// Custom hook: the nearest [`StripeJsProvider`]'s config, or the one set
+/// with [`set_stripejs_config`].
+#[hook]
+pub fn use_stripejs_config() -> StripeJsConfig {
+    use_context::<StripeJsConfig>().unwrap_or_else(stripejs_config)
+}
+
+/// Where loading Stripe.js stands.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StripeJsStatus {
+    /// The script is being fetched and parsed.
+    Loading,
+    /// `window.Stripe` exists.
+    Ready,
+    /// The script failed to load (ad blocker, CSP, network) or timed out.
+    Failed(String),
+}
+
+impl StripeJsStatus {
+    /// Whether Stripe.js can be used.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, StripeJsStatus::Ready)
+    }
+}
+
+/// Returned by [`use_stripejs_status`].
+///
+/// # Fields
+///
+/// * `status` – The current [`StripeJsStatus`].
+/// * `retry` – Remove the failed `<script>` and load it again.
+#[derive(Clone, PartialEq)]
+pub struct StripeJsLoad {
+    pub status: StripeJsStatus,
+    pub retry: Callback<()>,
+}
+
+/// Custom hook: load Stripe.js v3 exactly once and report failures.
+///
+/// # Arguments
+/// - `timeout_ms`: give up after this many milliseconds
+///   (default: the [`StripeJsConfig`]'s `timeout_ms`).
+///
+/// # Returns
+/// A [`StripeJsLoad`] whose `status` turns `Failed` when the script's
+/// `error` event fires or the timeout passes, with a `retry` action.
+///
+/// # Example
+/// ```rust,ignore
+/// let stripe_js = use_stripejs_status(None);
+/// match &stripe_js.status {
+///     StripeJsStatus::Loading => html! { <p>{ "Loading…" }</p> },
+///     StripeJsStatus::Ready => html! { <Checkout /> },
+///     StripeJsStatus::Failed(reason) => html! {
+///         <p>{ reason } <button onclick={stripe_js.retry.reform(|_| ())}>{ "Retry" }</button></p>
+///     },
+/// }
+/// ```
+#[hook]
+pub fn use_stripejs_status(timeout_ms: Option<u32>) -> StripeJsLoad {
+    let config = use_stripejs_config();
+    use_script_status(
+        Script::new(STRIPEJS_ID, config.script_url(), stripe_defined, &config),
+        timeout_ms.unwrap_or(config.timeout_ms),
+    )
+}
+
+/// Load Stripe.js outside a component, sharing the hooks' loader.
+///
+/// Resolves at once if Stripe.js is already there; a previous failed attempt
+/// is retried. See [`load_stripe`](crate::client::load_stripe) to also
+/// create a client.
+///
+/// Uses the config from [`set_stripejs_config`].
+///
+/// # Arguments
+/// - `timeout_ms`: give up after this many milliseconds
+///   (default: the config's `timeout_ms`).
+///
+/// # Errors
+/// `Err(StripeError)` with the reason if the script fails or times out, or
+/// there is no document to add it to (e.g. in a worker).
+pub async fn load_stripejs(timeout_ms: Option<u32>) -> Result<(), StripeError> {
+    let config = stripejs_config();
+    wait_for_script(
+        Script::new(STRIPEJS_ID, config.script_url(), stripe_defined, &config),
+        timeout_ms.unwrap_or(config.timeout_ms),
+    )
+    .await
+}
+
+/// Custom hook: load Stripe's `pricing-table.js` exactly once.
+///
+/// # Returns
+/// - `true` once `<stripe-pricing-table>` is defined.
+///
+/// Rendering the element earlier is fine; the browser upgrades it on load.
+#[hook]
+pub fn use_stripe_pricing_table_js() -> bool {
+    use_script(
+        "stripe-pricing-table-js",
+        "https://js.stripe.com/v3/pricing-table.js",
+        || custom_element_defined("stripe-pricing-table"),
+    )
+}
+
+/// Custom hook: load Stripe's `buy-button.js` exactly once.
+///
+/// # Returns
+/// - `true` once `<stripe-buy-button>` is defined.
+#[hook]
+pub fn use_stripe_buy_button_js() -> bool {
+    use_script(
+        "stripe-buy-button-js",
+        "https://js.stripe.com/v3/buy-button.js",
+        || custom_element_defined("stripe-buy-button"),
+    )
+}
+
+/// Custom hook: load Connect.js exactly once.
+///
+/// # Returns
+/// - `true` once `window.StripeConnect` exists, i.e. Connect embedded
+///   components can be initialized.
+#[hook]
+pub fn use_connectjs() -> bool {
+    use_script(
+        "stripe-connect-js",
+        "https://connect-js.stripe.com/v1.0/connect.js",
+        || {
+            web_sys::window()
+                .and_then(|win| Reflect::has(&win, &JsValue::from_str("StripeConnect")).ok())
+                .unwrap_or(false)
+        },
+    )
+}
+
+/// Inject `<script id={id} src={src} defer>` into `<head>` once and track
+/// readiness through the shared loader.
+#[hook]
+fn use_script(id: &'static str, src: &'static str, is_loaded: fn() -> bool) -> bool {
+    let config = use_stripejs_config();
+    use_script_status(
+        Script::new(id, src.to_string(), is_loaded, &config),
+        config.timeout_ms,
+    )
+    .status
+    .is_ready()
+}
+
+/// [`use_script`], reporting an `error` event or a load slower than
+/// `timeout_ms` as [`StripeJsStatus::Failed`].
+///
+/// Every hook instance subscribes to the same per-script loader, so one
+/// mounted while another is still loading the script hears the outcome too.
+#[hook]
+fn use_script_status(script: Script, timeout_ms: u32) -> StripeJsLoad {
+    let Script { id, is_loaded, .. } = script;
+    // Initialize state: check if the script already ran
+    let status = use_state_eq(|| {
+        if is_loaded() {
+            StripeJsStatus::Ready
+        } else {
+            current_status(id).unwrap_or(StripeJsStatus::Loading)
+        }
     });
 
     {
-        let loaded = loaded.clone();
-        use_effect(move || {
-            // If not yet loaded, inject the Stripe.js script once
-            if !*loaded {
-                let document = web_sys::window()
-                    .expect("no window")
-                    .document()
-                    .expect("no document");
-
-                // Only inject if `<script id="stripejs-sdk">` missing
-                if document.get_element_by_id("stripejs-sdk").is_none() {
-                    let script: HtmlScriptElement = document
-                        .create_element("script")
-                        .expect("create script")
-                        .dyn_into()
-                        .expect("cast script");
-
-                    script.set_id("stripejs-sdk");
-                    script.set_src("https://js.stripe.com/v3/");
-                    script.set_defer(true);
-
-                    // Closure to run on script.load → set loaded = true
-                    let onload_closure = Closure::wrap(Box::new(move || {
-                        loaded.set(true);
-                    }) as Box<dyn Fn()>);
-
-                    script.set_onload(Some(onload_closure.as_ref().unchecked_ref()));
-                    onload_closure.forget(); // Leak so it lives until load event
-
-                    document
-                        .head()
-                        .expect("head missing")
-                        .append_child(&script)
-                        .expect("append script");
-                }
+        let status = status.clone();
+        let script = script.clone();
+        use_effect_with((), move |_| {
+            let subscription = {
+                let status = status.clone();
+                subscribe(id, Rc::new(move |s: &StripeJsStatus| status.set(s.clone())))
+            };
+            status.set(start(&script, timeout_ms));
+            move || unsubscribe(id, subscription)
+        });
+    }
+
+    StripeJsLoad {
+        status: (*status).clone(),
+        retry: Callback::from(move |_: ()| retry(&script, timeout_ms)),
+    }
+}
+
+/// Wait for `script` outside a component, retrying it if an earlier attempt failed.
+async fn wait_for_script(script: Script, timeout_ms: u32) -> Result<(), StripeError> {
+    retry(&script, timeout_ms);
+    let mut subscription = None;
+    let promise = Promise::new(&mut |resolve, _reject| {
+        let settle: Subscriber = Rc::new(move |status: &StripeJsStatus| match status {
+            StripeJsStatus::Loading => {}
+            StripeJsStatus::Ready => {
+                let _ = resolve.call0(&JsValue::NULL);
+            }
+            StripeJsStatus::Failed(reason) => {
+                let _ = resolve.call1(&JsValue::NULL, &JsValue::from_str(reason));
             }
-            // No cleanup needed
-            || ()
         });
+        subscription = Some(subscribe(script.id, settle.clone()));
+        settle(&start(&script, timeout_ms));
+    });
+    let outcome = JsFuture::from(promise).await;
+    if let Some(subscription) = subscription {
+        unsubscribe(script.id, subscription);
+    }
+    match outcome.ok().and_then(|v| v.as_string()) {
+        Some(reason) => Err(StripeError {
+            message: reason,
+            error_type: None,
+            code: None,
+        }),
+        None => Ok(()),
+    }
+}
+
+const STRIPEJS_ID: &str = "stripejs-sdk";
+
+/// A script the loader knows how to inject and recognise once it has run.
+#[derive(Clone)]
+struct Script {
+    id: &'static str,
+    is_loaded: fn() -> bool,
+    tag: Rc<ScriptTag>,
+}
+
+/// What goes into the `<script>` tag and the preconnect hints before it.
+struct ScriptTag {
+    src: String,
+    nonce: Option<String>,
+    cross_origin: Option<String>,
+    preconnect: Vec<String>,
+}
+
+impl Script {
+    fn new(
+        id: &'static str,
+        src: String,
+        is_loaded: fn() -> bool,
+        config: &StripeJsConfig,
+    ) -> Self {
+        Script {
+            id,
+            is_loaded,
+            tag: Rc::new(ScriptTag {
+                src,
+                nonce: config.nonce.clone(),
+                cross_origin: config.cross_origin.clone(),
+                preconnect: config.preconnect.clone(),
+            }),
+        }
+    }
+}
+
+type Subscriber = Rc<dyn Fn(&StripeJsStatus)>;
+
+/// Process-wide state for one script, shared by every hook and future.
+#[derive(Default)]
+struct ScriptLoader {
+    /// `None` until something asks for the script.
+    status: Option<StripeJsStatus>,
+    next_subscription: usize,
+    subscribers: Vec<(usize, Subscriber)>,
+    element: Option<Element>,
+    /// `load`, `error` and timeout handlers for the current attempt.
+    handlers: Vec<Closure<dyn Fn()>>,
+    timer: Option<i32>,
+}
+
+thread_local! {
+    static LOADERS: RefCell<HashMap<&'static str, ScriptLoader>> = RefCell::new(HashMap::new());
+}
+
+fn with_loader<R>(id: &'static str, f: impl FnOnce(&mut ScriptLoader) -> R) -> R {
+    LOADERS.with(|loaders| f(loaders.borrow_mut().entry(id).or_default()))
+}
+
+fn current_status(id: &'static str) -> Option<StripeJsStatus> {
+    with_loader(id, |loader| loader.status.clone())
+}
+
+fn subscribe(id: &'static str, subscriber: Subscriber) -> usize {
+    with_loader(id, |loader| {
+        loader.next_subscription += 1;
+        loader
+            .subscribers
+            .push((loader.next_subscription, subscriber));
+        loader.next_subscription
+    })
+}
+
+fn unsubscribe(id: &'static str, subscription: usize) {
+    with_loader(id, |loader| {
+        loader.subscribers.retain(|(s, _)| *s != subscription)
+    });
+}
+
+/// Tell every subscriber, outside the loader borrow so they may resubscribe.
+fn notify(id: &'static str, status: &StripeJsStatus) {
+    let subscribers: Vec<Subscriber> = with_loader(id, |loader| {
+        loader.subscribers.iter().map(|(_, s)| s.clone()).collect()
+    });
+    for subscriber in subscribers {
+        subscriber(status);
+    }
+}
+
+/// Start loading `script` unless it is already loading, loaded or failed.
+fn start(script: &Script, timeout_ms: u32) -> StripeJsStatus {
+    if (script.is_loaded)() {
+        settle(script.id, StripeJsStatus::Ready);
+        return StripeJsStatus::Ready;
+    }
+    if let Some(status) = current_status(script.id) {
+        return status;
+    }
+    with_loader(script.id, |loader| {
+        loader.status = Some(StripeJsStatus::Loading)
+    });
+    if let Err(reason) = inject(script, timeout_ms) {
+        settle(script.id, StripeJsStatus::Failed(reason));
+    }
+    current_status(script.id).unwrap_or(StripeJsStatus::Loading)
+}
+
+/// Remove a failed `<script>` and load it again.
+fn retry(script: &Script, timeout_ms: u32) {
+    let failed = with_loader(script.id, |loader| {
+        if !matches!(loader.status, Some(StripeJsStatus::Failed(_))) {
+            return false;
+        }
+        // A tag still loading after a timeout must not call the handlers
+        // dropped below.
+        detach(loader);
+        if let Some(element) = loader.element.take() {
+            element.remove();
+        }
+        loader.status = Some(StripeJsStatus::Loading);
+        true
+    });
+    if failed {
+        // A tag added by someone else, under the same id.
+        if let Some(stale) = web_sys::window()
+            .and_then(|win| win.document())
+            .and_then(|doc| doc.get_element_by_id(script.id))
+        {
+            stale.remove();
+        }
+        notify(script.id, &StripeJsStatus::Loading);
+        if let Err(reason) = inject(script, timeout_ms) {
+            settle(script.id, StripeJsStatus::Failed(reason));
+        }
     }
+}
+
+/// Find or create the `<script>` and listen for its outcome.
+///
+/// Fails, with the reason, outside a browser page (e.g. in a worker).
+fn inject(script: &Script, timeout_ms: u32) -> Result<(), String> {
+    let unavailable = |what: &str| format!("Cannot load {}: {what}.", script.tag.src);
+    let window = web_sys::window().ok_or_else(|| unavailable("no window"))?;
+    let document = window
+        .document()
+        .ok_or_else(|| unavailable("no document"))?;
+    let head = document
+        .head()
+        .ok_or_else(|| unavailable("the document has no <head>"))?;
+
+    for origin in &script.tag.preconnect {
+        let selector = format!("link[rel=\"preconnect\"][href=\"{origin}\"]");
+        if let Ok(None) = document.query_selector(&selector) {
+            if let Ok(link) = document.create_element("link") {
+                let _ = link.set_attribute("rel", "preconnect");
+                let _ = link.set_attribute("href", origin);
+                let _ = head.append_child(&link);
+            }
+        }
+    }
+
+    // Reuse `<script id=…>` if present, otherwise inject it
+    let element = match document.get_element_by_id(script.id) {
+        Some(existing) => existing,
+        None => {
+            let tag: HtmlScriptElement = document
+                .create_element("script")
+                .ok()
+                .and_then(|el| el.dyn_into().ok())
+                .ok_or_else(|| unavailable("could not create a <script> element"))?;
+            tag.set_id(script.id);
+            tag.set_src(&script.tag.src);
+            tag.set_defer(true);
+            if let Some(nonce) = &script.tag.nonce {
+                let _ = tag.set_attribute("nonce", nonce);
+            }
+            if let Some(cross_origin) = &script.tag.cross_origin {
+                tag.set_cross_origin(Some(cross_origin));
+            }
+            head.append_child(&tag)
+                .map_err(|_| unavailable("could not add the <script> to <head>"))?;
+            tag.into()
+        }
+    };
+
+    let Script { id, is_loaded, .. } = *script;
+    let src = script.tag.src.clone();
+    let on_load =
+        Closure::wrap(Box::new(move || settle(id, StripeJsStatus::Ready)) as Box<dyn Fn()>);
+    let on_error = {
+        let src = src.clone();
+        Closure::wrap(Box::new(move || {
+            settle(
+                id,
+                StripeJsStatus::Failed(format!(
+                    "Could not load {src}. It may be blocked by an ad blocker, \
+                 a Content-Security-Policy or the network."
+                )),
+            )
+        }) as Box<dyn Fn()>)
+    };
+    let on_timeout = Closure::wrap(Box::new(move || {
+        if is_loaded() {
+            settle(id, StripeJsStatus::Ready);
+        } else {
+            time_out(id, format!("{src} did not load within {timeout_ms} ms."));
+        }
+    }) as Box<dyn Fn()>);
+    let _ = element.add_event_listener_with_callback("load", on_load.as_ref().unchecked_ref());
+    let _ = element.add_event_listener_with_callback("error", on_error.as_ref().unchecked_ref());
+    let timer = window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            on_timeout.as_ref().unchecked_ref(),
+            timeout_ms as i32,
+        )
+        .ok();
 
-    *loaded
+    // Replacing the previous attempt's handlers drops them; none of them is
+    // running, since `inject` is only reached from `start` or `retry`.
+    with_loader(script.id, |loader| {
+        loader.element = Some(element);
+        loader.handlers = vec![on_load, on_error, on_timeout];
+        loader.timer = timer;
+    });
+    Ok(())
+}
+
+/// Record the outcome of the current attempt and tell subscribers.
+/// Later events (a timeout after `load`, say) are ignored.
+fn settle(id: &'static str, status: StripeJsStatus) {
+    let changed = with_loader(id, |loader| {
+        // A script that turns up after timing out still counts as loaded.
+        let settled = match (&loader.status, &status) {
+            (Some(StripeJsStatus::Ready), _) => true,
+            (Some(StripeJsStatus::Failed(_)), StripeJsStatus::Ready) => false,
+            (Some(StripeJsStatus::Failed(_)), _) => true,
+            _ => false,
+        };
+        if settled {
+            return false;
+        }
+        loader.status = Some(status.clone());
+        detach(loader);
+        clear_timer(loader);
+        true
+    });
+    if changed {
+        notify(id, &status);
+    }
+}
+
+/// Report a timeout but keep listening: a slow script that loads later
+/// still moves every subscriber to `Ready`.
+fn time_out(id: &'static str, reason: String) {
+    let changed = with_loader(id, |loader| {
+        if loader.status != Some(StripeJsStatus::Loading) {
+            return false;
+        }
+        loader.status = Some(StripeJsStatus::Failed(reason.clone()));
+        loader.timer = None;
+        true
+    });
+    if changed {
+        notify(id, &StripeJsStatus::Failed(reason));
+    }
+}
+
+/// Detach the `load`/`error` listeners; the closures themselves are kept
+/// until the next attempt, as one of them may be the caller.
+fn detach(loader: &ScriptLoader) {
+    if let (Some(element), [on_load, on_error, ..]) = (&loader.element, loader.handlers.as_slice())
+    {
+        let _ =
+            element.remove_event_listener_with_callback("load", on_load.as_ref().unchecked_ref());
+        let _ =
+            element.remove_event_listener_with_callback("error", on_error.as_ref().unchecked_ref());
+    }
+}
+
+fn clear_timer(loader: &mut ScriptLoader) {
+    if let (Some(timer), Some(window)) = (loader.timer.take(), web_sys::window()) {
+        window.clear_timeout_with_handle(timer);
+    }
+}
+
+/// Whether `window.Stripe` exists.
+fn stripe_defined() -> bool {
+    web_sys::window()
+        .and_then(|win| {
+            Reflect::has(&win, &JsValue::from_str("Stripe"))
+                .ok()
+                .filter(|&b| b) // keep only `true`
+        })
+        .map(|_| true)
+        .unwrap_or(false)
+}
+
+/// Whether `customElements.get(name)` returns a constructor.
+fn custom_element_defined(name: &str) -> bool {
+    let Some(win) = web_sys::window() else {
+        return false;
+    };
+    let Ok(registry) = Reflect::get(&win, &JsValue::from_str("customElements")) else {
+        return false;
+    };
+    let Ok(get) = Reflect::get(&registry, &JsValue::from_str("get")) else {
+        return false;
+    };
+    get.dyn_into::<Function>()
+        .ok()
+        .and_then(|get| get.call1(&registry, &JsValue::from_str(name)).ok())
+        .is_some_and(|ctor| !ctor.is_undefined())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(src: &str, advanced_fraud_signals: bool) -> StripeJsConfig {
+        StripeJsConfig {
+            src: src.into(),
+            advanced_fraud_signals,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn script_url_keeps_src_with_fraud_signals() {
+        assert_eq!(StripeJsConfig::default().script_url(), STRIPEJS_URL);
+        assert_eq!(
+            config("/stripe.js?v=2", true).script_url(),
+            "/stripe.js?v=2"
+        );
+    }
+
+    #[test]
+    fn script_url_starts_query_for_fraud_signals_opt_out() {
+        assert_eq!(
+            config("https://js.stripe.com/v3/", false).script_url(),
+            "https://js.stripe.com/v3/?advancedFraudSignals=false"
+        );
+    }
+
+    #[test]
+    fn script_url_extends_existing_query_for_fraud_signals_opt_out() {
+        assert_eq!(
+            config("/stripe.js?v=2", false).script_url(),
+            "/stripe.js?v=2&advancedFraudSignals=false"
+        );
+    }
 }
@@ -0,0 +1,220 @@
+//! A Yew component that securely displays an Issuing card's details.
+//!
+//! The card number, CVC, expiry and PIN render inside Stripe-hosted iframes,
+//! so sensitive data never touches your app or servers. [`IssuingCardDisplay`]
+//! creates the ephemeral-key nonce, asks your server for the ephemeral key
+//! through `fetch_ephemeral_key`, and mounts the display elements with copy
+//! buttons.
+
+use crate::client::{
+    create_ephemeral_key_nonce, create_issuing_element, js_to_stripe_error, FetchEphemeralKey,
+    IssuingElement, IssuingElementOptions, StripeClient, StripeError, StripeOptions,
+};
+use crate::{use_stripejs, JsPaymentElement};
+use std::cell::Cell;
+use std::rc::Rc;
+use web_sys::js_sys::Object;
+use web_sys::Element;
+use yew::prelude::*;
+
+/// Properties for the [`IssuingCardDisplay`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `stripe_options` – `locale`, `stripeAccount`, etc. for the Stripe instance.
+/// * `card_id` – The Issuing card to show (`ic_…`).
+/// * `fetch_ephemeral_key` – Given the card id and nonce, creates an ephemeral
+///   key on your server and returns its secret.
+/// * `show_pin` – Also display the PIN (default: `false`).
+/// * `copy_buttons` – Render copy buttons next to number, expiry and CVC (default: `true`).
+/// * `style` – Style applied to every element, e.g. `{ "base": { "fontSize": "16px" } }`.
+/// * `on_error` – Callback invoked with [`StripeError`] on failure.
+/// * `class` – Classes for the container element.
+#[derive(Properties, PartialEq, Clone)]
+pub struct IssuingCardDisplayProps {
+    pub publishable_key: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    pub card_id: String,
+    pub fetch_ephemeral_key: FetchEphemeralKey,
+    #[prop_or(false)]
+    pub show_pin: bool,
+    #[prop_or(true)]
+    pub copy_buttons: bool,
+    #[prop_or_default]
+    pub style: Option<serde_json::Value>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// Yew function component showing an Issuing card's number, expiry, CVC and PIN.
+///
+/// A new `card_id`, or toggling `show_pin` or `copy_buttons`, unmounts the
+/// elements and mounts a fresh set with a new ephemeral key.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let fetch = use_memo((), |_| FetchEphemeralKey::new(|card_id, nonce| async move {
+///     create_ephemeral_key(&card_id, &nonce).await
+/// }));
+/// html! {
+///     <IssuingCardDisplay
+///         publishable_key="pk_test_123".to_string()
+///         card_id={card.id.clone()}
+///         fetch_ephemeral_key={(*fetch).clone()}
+///     />
+/// }
+/// ```
+#[function_component(IssuingCardDisplay)]
+pub fn issuing_card_display(props: &IssuingCardDisplayProps) -> Html {
+    let stripe_ready = use_stripejs();
+    let number = use_node_ref();
+    let expiry = use_node_ref();
+    let cvc = use_node_ref();
+    let pin = use_node_ref();
+    let number_copy = use_node_ref();
+    let expiry_copy = use_node_ref();
+    let cvc_copy = use_node_ref();
+    let mounted = use_mut_ref(Vec::<JsPaymentElement>::new);
+    let error = use_state(|| None::<String>);
+
+    {
+        let slots = [
+            (IssuingElement::CardNumber, None, number.clone()),
+            (IssuingElement::Expiry, None, expiry.clone()),
+            (IssuingElement::Cvc, None, cvc.clone()),
+            (IssuingElement::Pin, None, pin.clone()),
+            (
+                IssuingElement::CopyButton,
+                Some("number"),
+                number_copy.clone(),
+            ),
+            (
+                IssuingElement::CopyButton,
+                Some("expiry"),
+                expiry_copy.clone(),
+            ),
+            (IssuingElement::CopyButton, Some("cvc"), cvc_copy.clone()),
+        ];
+        let mounted = mounted.clone();
+        let error = error.clone();
+        let props = props.clone();
+        use_effect_with(
+            (
+                stripe_ready,
+                props.card_id.clone(),
+                props.show_pin,
+                props.copy_buttons,
+            ),
+            move |(ready, card_id, _, _)| {
+                let alive = Rc::new(Cell::new(true));
+                if *ready {
+                    let alive = alive.clone();
+                    let mounted = mounted.clone();
+                    let card_id = card_id.clone();
+                    error.set(None);
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let mut created = Vec::new();
+                        let result = async {
+                            let client = StripeClient::from_options(
+                                &props.publishable_key,
+                                props.stripe_options.as_ref(),
+                            )?;
+                            let nonce = create_ephemeral_key_nonce(client.js(), &card_id).await?;
+                            let secret = props.fetch_ephemeral_key.call(&card_id, &nonce).await?;
+                            let elements = client
+                                .js()
+                                .elements(Object::new().into())
+                                .map_err(js_to_stripe_error)?;
+                            // Slots that aren't rendered (PIN, copy buttons) have no node.
+                            for (kind, to_copy, node) in &slots {
+                                let Some(target) = node.cast::<Element>() else {
+                                    continue;
+                                };
+                                let options = match to_copy {
+                                    Some(field) => IssuingElementOptions {
+                                        to_copy: Some(field.to_string()),
+                                        style: props.style.clone(),
+                                        ..Default::default()
+                                    },
+                                    None => IssuingElementOptions {
+                                        issuing_card: Some(card_id.clone()),
+                                        nonce: Some(nonce.clone()),
+                                        ephemeral_key_secret: Some(secret.clone()),
+                                        style: props.style.clone(),
+                                        ..Default::default()
+                                    },
+                                };
+                                let el = create_issuing_element(&elements, *kind, &options)?;
+                                let mount = el.mount_element(&target);
+                                created.push(el);
+                                mount.map_err(js_to_stripe_error)?;
+                            }
+                            Ok::<_, StripeError>(())
+                        }
+                        .await;
+                        if alive.get() && result.is_ok() {
+                            *mounted.borrow_mut() = created;
+                            return;
+                        }
+                        // Unmounted meanwhile, or failed partway: destroy what was created.
+                        for el in created {
+                            let _ = el.destroy();
+                        }
+                        if let (true, Err(e)) = (alive.get(), result) {
+                            props.on_error.emit(e.clone());
+                            error.set(Some(e.message));
+                        }
+                    });
+                }
+                move || {
+                    alive.set(false);
+                    for el in mounted.borrow_mut().drain(..) {
+                        let _ = el.destroy();
+                    }
+                }
+            },
+        );
+    }
+
+    let row = |label: &str, value: &NodeRef, copy: &NodeRef| {
+        html! {
+            <div class="flex flex-col gap-1">
+                <span class="text-xs uppercase tracking-wide text-slate-500">{ label.to_string() }</span>
+                <div class="flex items-center gap-2">
+                    <div class="flex-1" ref={value.clone()}></div>
+                    if props.copy_buttons {
+                        <div ref={copy.clone()}></div>
+                    }
+                </div>
+            </div>
+        }
+    };
+
+    html! {
+        <div class={classes!("flex", "flex-col", "gap-3", props.class.clone())}>
+            { row("Card number", &number, &number_copy) }
+            <div class="grid grid-cols-2 gap-3">
+                { row("Expiry", &expiry, &expiry_copy) }
+                { row("CVC", &cvc, &cvc_copy) }
+            </div>
+            if props.show_pin {
+                <div class="flex flex-col gap-1">
+                    <span class="text-xs uppercase tracking-wide text-slate-500">{ "PIN" }</span>
+                    <div ref={pin.clone()}></div>
+                </div>
+            }
+            {
+                if let Some(msg) = &*error {
+                    html!{ <div class="text-red-500 text-sm">{ msg }</div> }
+                } else {
+                    Html::default()
+                }
+            }
+        </div>
+    }
+}
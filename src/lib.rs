@@ -1,8 +1,33 @@
+mod bank_account_component;
 mod bindings;
 mod checkout_component;
 pub mod client;
+mod connect_component;
+mod custom_checkout_component;
+mod elements_component;
+mod embedded_checkout_component;
+mod financial_connections_component;
+mod identity_component;
 mod interop;
+mod issuing_component;
+mod messaging_component;
+mod next_action_component;
+mod pricing_table_component;
+mod return_handler;
+mod stripe_action;
 
+pub use bank_account_component::*;
 pub use bindings::*;
 pub use checkout_component::*;
+pub use connect_component::*;
+pub use custom_checkout_component::*;
+pub use elements_component::*;
+pub use embedded_checkout_component::*;
+pub use financial_connections_component::*;
+pub use identity_component::*;
 pub use interop::*;
+pub use issuing_component::*;
+pub use messaging_component::*;
+pub use next_action_component::*;
+pub use pricing_table_component::*;
+pub use return_handler::*;
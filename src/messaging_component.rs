@@ -0,0 +1,139 @@
+//! A Yew component for the Payment Method Messaging Element.
+//!
+//! Shows "Pay in 4 interest-free payments of $12.50 with Klarna" style
+//! messaging on product and cart pages. It needs only a publishable key —
+//! no PaymentIntent or client secret — and follows the `amount` prop, so
+//! price or quantity changes update the mounted element in place.
+
+use crate::client::{
+    create_payment_method_messaging_element, js_to_stripe_error,
+    update_payment_method_messaging_element, PaymentMethodMessagingOptions, StripeClient,
+    StripeError, StripeOptions,
+};
+use crate::{use_stripejs, JsPaymentElement};
+use web_sys::Element;
+use yew::prelude::*;
+
+/// Properties for the [`StripePaymentMethodMessaging`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `stripe_options` – `locale`, `stripeAccount`, etc. for the Stripe instance.
+/// * `amount` – Price in the currency's smallest unit, e.g. `4999` for $49.99.
+/// * `currency` – Three-letter ISO currency code, e.g. `"usd"`.
+/// * `payment_method_types` – BNPL methods to promote; empty for Stripe's defaults.
+/// * `country_code` – The customer's two-letter country code, e.g. `"US"`.
+/// * `on_error` – Callback invoked with [`StripeError`] if the element cannot be created or updated.
+/// * `class` – Classes for the container element.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripePaymentMethodMessagingProps {
+    pub publishable_key: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    pub amount: i64,
+    pub currency: String,
+    #[prop_or_default]
+    pub payment_method_types: Vec<String>,
+    #[prop_or_default]
+    pub country_code: Option<String>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+impl StripePaymentMethodMessagingProps {
+    fn options(&self) -> PaymentMethodMessagingOptions {
+        PaymentMethodMessagingOptions {
+            amount: self.amount,
+            currency: self.currency.clone(),
+            payment_method_types: self.payment_method_types.clone(),
+            country_code: self.country_code.clone(),
+        }
+    }
+}
+
+/// Yew function component rendering BNPL promotional messaging.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// html! {
+///     <StripePaymentMethodMessaging
+///         publishable_key="pk_test_123".to_string()
+///         amount={price * (*quantity as i64)}
+///         currency="usd".to_string()
+///         payment_method_types={vec!["klarna".into(), "afterpay_clearpay".into()]}
+///         country_code={Some("US".to_string())}
+///     />
+/// }
+/// ```
+#[function_component(StripePaymentMethodMessaging)]
+pub fn stripe_payment_method_messaging(props: &StripePaymentMethodMessagingProps) -> Html {
+    let stripe_ready = use_stripejs();
+    let container = use_node_ref();
+    let element = use_mut_ref(|| None::<JsPaymentElement>);
+    let options = props.options();
+
+    // Create and mount once Stripe.js is ready; recreate if the Stripe
+    // instance settings change.
+    {
+        let container = container.clone();
+        let element = element.clone();
+        let options = options.clone();
+        let on_error = props.on_error.clone();
+        use_effect_with(
+            (
+                stripe_ready,
+                props.publishable_key.clone(),
+                props.stripe_options.clone(),
+            ),
+            move |(ready, publishable_key, stripe_options)| {
+                if *ready {
+                    let client =
+                        StripeClient::from_options(publishable_key, stripe_options.as_ref());
+                    let created = client.and_then(|client| {
+                        create_payment_method_messaging_element(client.js(), &options)
+                    });
+                    let mounted = created.and_then(|el| {
+                        if let Some(target) = container.cast::<Element>() {
+                            if let Err(e) = el.mount_element(&target) {
+                                let _ = el.destroy();
+                                return Err(js_to_stripe_error(e));
+                            }
+                        }
+                        Ok(el)
+                    });
+                    match mounted {
+                        Ok(el) => *element.borrow_mut() = Some(el),
+                        Err(e) => on_error.emit(e),
+                    }
+                }
+                move || {
+                    if let Some(el) = element.borrow_mut().take() {
+                        let _ = el.destroy();
+                    }
+                }
+            },
+        );
+    }
+
+    // Push amount/currency/type changes into the mounted element.
+    {
+        let element = element.clone();
+        let on_error = props.on_error.clone();
+        use_effect_with(options, move |options| {
+            if let Some(el) = element.borrow().as_ref() {
+                if let Err(e) = update_payment_method_messaging_element(el, options) {
+                    on_error.emit(e);
+                }
+            }
+            || ()
+        });
+    }
+
+    html! {
+        <div class={props.class.clone()} ref={container}></div>
+    }
+}
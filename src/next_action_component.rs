@@ -0,0 +1,193 @@
+//! A Yew component that shows the customer how to finish a `requires_action` payment.
+//!
+//! Voucher methods (OXXO, Boleto, Konbini) and QR-code methods (PayNow,
+//! PromptPay, WeChat Pay) leave the PaymentIntent in `requires_action` until
+//! the customer pays in a store or with their banking app. [`StripeNextAction`]
+//! renders the voucher number or QR code from the intent's typed
+//! [`NextAction`], plus a link to Stripe's hosted instructions. For ACH
+//! microdeposits it links to hosted verification; use
+//! [`StripeMicrodepositForm`](crate::StripeMicrodepositForm) to verify in-app.
+
+use crate::client::{
+    BoletoDisplayDetails, KonbiniDisplayDetails, KonbiniStore, NextAction, OxxoDisplayDetails,
+    QrCodeDetails, VerifyWithMicrodeposits,
+};
+use wasm_bindgen::JsValue;
+use web_sys::js_sys::Date;
+use yew::prelude::*;
+
+/// Properties for the [`StripeNextAction`] component.
+///
+/// # Fields
+///
+/// * `next_action` – The intent's `next_action`.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeNextActionProps {
+    pub next_action: NextAction,
+}
+
+/// Yew function component rendering voucher or QR-code instructions.
+///
+/// Renders nothing for [`NextAction::Other`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// if let Some(next_action) = intent.next_action.clone() {
+///     html! { <StripeNextAction {next_action} /> }
+/// }
+/// ```
+#[function_component(StripeNextAction)]
+pub fn stripe_next_action(props: &StripeNextActionProps) -> Html {
+    let body = match &props.next_action {
+        NextAction::RedirectToUrl(r) => match &r.url {
+            Some(url) => html! {
+                <a href={url.clone()} class="text-blue-600 underline">
+                    { "Continue to complete your payment" }
+                </a>
+            },
+            None => Html::default(),
+        },
+        NextAction::DisplayOxxoDetails(d) => oxxo(d),
+        NextAction::BoletoDisplayDetails(d) => boleto(d),
+        NextAction::KonbiniDisplayDetails(d) => konbini(d),
+        NextAction::PaynowDisplayQrCode(d) => qr_code("PayNow", d),
+        NextAction::PromptpayDisplayQrCode(d) => qr_code("PromptPay", d),
+        NextAction::WechatPayDisplayQrCode(d) => qr_code("WeChat Pay", d),
+        NextAction::VerifyWithMicrodeposits(d) => microdeposits(d),
+        NextAction::Other { .. } => return Html::default(),
+    };
+
+    html! {
+        <div class="flex flex-col gap-2 items-center w-full rounded border border-slate-200 p-4" role="status">
+            { body }
+        </div>
+    }
+}
+
+fn oxxo(d: &OxxoDisplayDetails) -> Html {
+    html! {
+        <>
+            <div class="font-semibold">{ "Pay your OXXO voucher in store to complete your order." }</div>
+            { labelled("Reference", d.number.as_deref()) }
+            { expiry(d.expires_after) }
+            { link("View voucher", d.hosted_voucher_url.as_deref()) }
+        </>
+    }
+}
+
+fn boleto(d: &BoletoDisplayDetails) -> Html {
+    html! {
+        <>
+            <div class="font-semibold">{ "Pay your boleto to complete your order." }</div>
+            { labelled("Boleto number", d.number.as_deref()) }
+            { expiry(d.expires_at) }
+            { link("View boleto", d.hosted_voucher_url.as_deref()) }
+            { link("Download PDF", d.pdf.as_deref()) }
+        </>
+    }
+}
+
+fn konbini(d: &KonbiniDisplayDetails) -> Html {
+    let store = |name: &str, s: &Option<KonbiniStore>| match s {
+        Some(s) => html! {
+            <div class="text-sm">
+                <span class="font-semibold">{ name }</span>
+                { labelled("Payment code", s.payment_code.as_deref()) }
+                { labelled("Confirmation number", s.confirmation_number.as_deref()) }
+            </div>
+        },
+        None => Html::default(),
+    };
+    html! {
+        <>
+            <div class="font-semibold">{ "Pay at a convenience store to complete your order." }</div>
+            { store("FamilyMart", &d.stores.familymart) }
+            { store("Lawson", &d.stores.lawson) }
+            { store("Ministop", &d.stores.ministop) }
+            { store("Seicomart", &d.stores.seicomart) }
+            { expiry(d.expires_at) }
+            { link("View payment instructions", d.hosted_voucher_url.as_deref()) }
+        </>
+    }
+}
+
+fn qr_code(method: &str, d: &QrCodeDetails) -> Html {
+    let src = d
+        .image_url_svg
+        .as_ref()
+        .or(d.image_url_png.as_ref())
+        .or(d.image_data_url.as_ref());
+    html! {
+        <>
+            <div class="font-semibold">{ format!("Scan with {} to complete your payment.", method) }</div>
+            {
+                match src {
+                    Some(src) => html! {
+                        <img src={src.clone()} alt={format!("{} QR code", method)} class="w-48 h-48" />
+                    },
+                    None => Html::default(),
+                }
+            }
+            { link("View payment instructions", d.hosted_instructions_url.as_deref()) }
+        </>
+    }
+}
+
+fn microdeposits(d: &VerifyWithMicrodeposits) -> Html {
+    html! {
+        <>
+            <div class="font-semibold">
+                { "We sent small deposits to your bank account. Enter them to verify it." }
+            </div>
+            {
+                match d.arrival_date {
+                    Some(ts) => html! {
+                        <div class="text-sm text-slate-600">{ format!("Expected by {}", format_timestamp(ts)) }</div>
+                    },
+                    None => Html::default(),
+                }
+            }
+            { link("Verify your bank account", d.hosted_verification_url.as_deref()) }
+        </>
+    }
+}
+
+fn labelled(label: &str, value: Option<&str>) -> Html {
+    match value {
+        Some(v) => html! {
+            <div class="text-sm">
+                { format!("{}: ", label) }
+                <span class="font-mono select-all">{ v }</span>
+            </div>
+        },
+        None => Html::default(),
+    }
+}
+
+fn expiry(timestamp: Option<i64>) -> Html {
+    match timestamp {
+        Some(ts) => html! {
+            <div class="text-sm text-slate-600">{ format!("Expires {}", format_timestamp(ts)) }</div>
+        },
+        None => Html::default(),
+    }
+}
+
+/// Format a Unix timestamp in the browser's locale.
+pub(crate) fn format_timestamp(ts: i64) -> String {
+    let date = Date::new(&JsValue::from_f64(ts as f64 * 1000.0));
+    date.to_locale_string("default", &JsValue::UNDEFINED).into()
+}
+
+fn link(label: &str, href: Option<&str>) -> Html {
+    match href {
+        Some(href) => html! {
+            <a href={href.to_string()} target="_blank" rel="noopener noreferrer"
+               class="text-blue-600 underline text-sm">
+                { label }
+            </a>
+        },
+        None => Html::default(),
+    }
+}
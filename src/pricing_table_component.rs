@@ -0,0 +1,106 @@
+//! Yew components for Stripe's no-code Pricing Table and Buy Button.
+//!
+//! Both render Stripe's custom elements (`<stripe-pricing-table>`,
+//! `<stripe-buy-button>`) and load their scripts through the same loader as
+//! [`use_stripejs`](crate::use_stripejs). Create the table or button in the
+//! Stripe Dashboard and pass its id here.
+
+use crate::{use_stripe_buy_button_js, use_stripe_pricing_table_js};
+use yew::prelude::*;
+
+/// Properties for the [`StripePricingTable`] component.
+///
+/// # Fields
+///
+/// * `pricing_table_id` – The table's id from the Dashboard (`prctbl_…`).
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `client_reference_id` – Your id for the customer or cart; copied to the
+///   Checkout Session for reconciliation.
+/// * `customer_email` – Prefills the email on the Checkout page.
+/// * `customer_session_client_secret` – A Customer Session secret, letting
+///   Stripe show an existing customer's saved details.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripePricingTableProps {
+    pub pricing_table_id: AttrValue,
+    pub publishable_key: AttrValue,
+    #[prop_or_default]
+    pub client_reference_id: Option<AttrValue>,
+    #[prop_or_default]
+    pub customer_email: Option<AttrValue>,
+    #[prop_or_default]
+    pub customer_session_client_secret: Option<AttrValue>,
+}
+
+/// Yew function component rendering `<stripe-pricing-table>`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// html! {
+///     <StripePricingTable
+///         pricing_table_id="prctbl_123"
+///         publishable_key="pk_test_123"
+///         client_reference_id={Some(user.id.clone().into())}
+///     />
+/// }
+/// ```
+#[function_component(StripePricingTable)]
+pub fn stripe_pricing_table(props: &StripePricingTableProps) -> Html {
+    use_stripe_pricing_table_js();
+
+    html! {
+        <stripe-pricing-table
+            pricing-table-id={props.pricing_table_id.clone()}
+            publishable-key={props.publishable_key.clone()}
+            client-reference-id={props.client_reference_id.clone()}
+            customer-email={props.customer_email.clone()}
+            customer-session-client-secret={props.customer_session_client_secret.clone()}
+        ></stripe-pricing-table>
+    }
+}
+
+/// Properties for the [`StripeBuyButton`] component.
+///
+/// # Fields
+///
+/// * `buy_button_id` – The button's id from the Dashboard (`buy_btn_…`).
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `client_reference_id` – Your id for the customer or cart.
+/// * `customer_email` – Prefills the email on the Checkout page.
+/// * `customer_session_client_secret` – A Customer Session secret for an
+///   existing customer.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeBuyButtonProps {
+    pub buy_button_id: AttrValue,
+    pub publishable_key: AttrValue,
+    #[prop_or_default]
+    pub client_reference_id: Option<AttrValue>,
+    #[prop_or_default]
+    pub customer_email: Option<AttrValue>,
+    #[prop_or_default]
+    pub customer_session_client_secret: Option<AttrValue>,
+}
+
+/// Yew function component rendering `<stripe-buy-button>`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// html! {
+///     <StripeBuyButton buy_button_id="buy_btn_123" publishable_key="pk_test_123" />
+/// }
+/// ```
+#[function_component(StripeBuyButton)]
+pub fn stripe_buy_button(props: &StripeBuyButtonProps) -> Html {
+    use_stripe_buy_button_js();
+
+    html! {
+        <stripe-buy-button
+            buy-button-id={props.buy_button_id.clone()}
+            publishable-key={props.publishable_key.clone()}
+            client-reference-id={props.client_reference_id.clone()}
+            customer-email={props.customer_email.clone()}
+            customer-session-client-secret={props.customer_session_client_secret.clone()}
+        ></stripe-buy-button>
+    }
+}
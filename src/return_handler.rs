@@ -0,0 +1,262 @@
+//! Handle the shopper's return from redirect-based payment methods.
+//!
+//! With `redirect: "if_required"`, methods such as iDEAL, Bancontact or Klarna
+//! send the shopper to their bank and back to your `return_url`, with
+//! `payment_intent`, `payment_intent_client_secret` and `redirect_status`
+//! appended to the query string. This module detects those params, retrieves
+//! the PaymentIntent, and reports the outcome exactly like [`StripeCheckout`](crate::StripeCheckout).
+//!
+//! - [`use_stripe_return`] is the hook form, returning a [`StripeReturnState`].
+//! - [`StripeReturnHandler`] is the component form, emitting `on_success`/`on_error`.
+
+use crate::checkout_component::{resolve_checkout, CheckoutOutcome, StripeCheckoutSuccess};
+use crate::client::{
+    current_return_params, NextAction, PaymentIntent, PollOptions, StripeClient, StripeError,
+    StripeOptions,
+};
+use crate::use_stripejs;
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+use yew::functional::hook;
+use yew::prelude::*;
+
+/// Progress of handling a redirect return.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StripeReturnState {
+    /// The current URL carries no Stripe redirect params.
+    NotReturning,
+    /// Params found; waiting for Stripe.js or the PaymentIntent.
+    Pending,
+    /// The PaymentIntent is still `processing` (bank debits, some wallets).
+    Processing(Box<PaymentIntent>),
+    /// The customer must still pay a voucher or scan a QR code.
+    RequiresAction(NextAction),
+    /// The PaymentIntent succeeded.
+    Succeeded(StripeCheckoutSuccess),
+    /// The PaymentIntent failed or could not be retrieved.
+    Failed(StripeError),
+}
+
+/// Custom hook: detect a Stripe redirect return in the current URL and
+/// resolve it to a [`StripeReturnState`].
+///
+/// Loads Stripe.js via [`use_stripejs`] and retrieves the PaymentIntent once,
+/// again if `publishable_key` or `stripe_options` change.
+///
+/// # Arguments
+///
+/// * `publishable_key` – Your Stripe publishable key (starts with `pk_`).
+/// * `stripe_options` – The same options used when the payment was started.
+/// * `poll_processing` – Keep polling a `processing` intent until it settles.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// match use_stripe_return("pk_test_…".into(), None, None) {
+///     StripeReturnState::NotReturning => html! { <Shop /> },
+///     StripeReturnState::Pending => html! { <p>{ "Confirming payment…" }</p> },
+///     StripeReturnState::Processing(_) => html! { <p>{ "Payment processing…" }</p> },
+///     StripeReturnState::RequiresAction(next_action) => html! { <StripeNextAction {next_action} /> },
+///     StripeReturnState::Succeeded(s) => html! { <Receipt info={s} /> },
+///     StripeReturnState::Failed(e) => html! { <p>{ e.message }</p> },
+/// }
+/// ```
+#[hook]
+pub fn use_stripe_return(
+    publishable_key: String,
+    stripe_options: Option<StripeOptions>,
+    poll_processing: Option<PollOptions>,
+) -> StripeReturnState {
+    let stripe_ready = use_stripejs();
+    let params = use_memo((), |_| current_return_params());
+    let state = use_state(|| match *params {
+        Some(_) => StripeReturnState::Pending,
+        None => StripeReturnState::NotReturning,
+    });
+
+    {
+        let state = state.clone();
+        use_effect_with(
+            (stripe_ready, publishable_key, stripe_options),
+            move |(ready, publishable_key, stripe_options)| {
+                let alive = Rc::new(Cell::new(true));
+                if let (true, Some(ret)) = (*ready, (*params).clone()) {
+                    let alive = alive.clone();
+                    let client =
+                        StripeClient::from_options(publishable_key, stripe_options.as_ref());
+                    state.set(StripeReturnState::Pending);
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let client = match client {
+                            Ok(client) => client,
+                            Err(e) => {
+                                if alive.get() {
+                                    state.set(StripeReturnState::Failed(e));
+                                }
+                                return;
+                            }
+                        };
+                        let on_processing = {
+                            let state = state.clone();
+                            let alive = alive.clone();
+                            Callback::from(move |intent| {
+                                if alive.get() {
+                                    state.set(StripeReturnState::Processing(Box::new(intent)))
+                                }
+                            })
+                        };
+                        let outcome = resolve_checkout(
+                            client.js(),
+                            &ret.payment_intent_client_secret,
+                            poll_processing.as_ref(),
+                            &on_processing,
+                        )
+                        .await;
+                        if !alive.get() {
+                            return;
+                        }
+                        state.set(match outcome {
+                            CheckoutOutcome::Succeeded(success) => {
+                                StripeReturnState::Succeeded(success)
+                            }
+                            CheckoutOutcome::Processing(intent) => {
+                                StripeReturnState::Processing(intent)
+                            }
+                            CheckoutOutcome::RequiresAction(a) => {
+                                StripeReturnState::RequiresAction(a)
+                            }
+                            CheckoutOutcome::Failed(e) => StripeReturnState::Failed(e),
+                        });
+                    });
+                }
+                move || alive.set(false)
+            },
+        );
+    }
+
+    (*state).clone()
+}
+
+/// Properties for the [`StripeReturnHandler`] component.
+///
+/// # Fields
+///
+/// * `publishable_key` – Your Stripe Publishable Key (`pk_…`).
+/// * `stripe_options` – The options used when the payment was started.
+/// * `on_success` – Callback invoked with [`StripeCheckoutSuccess`] on success.
+/// * `on_error` – Callback invoked with [`StripeError`] on failure.
+/// * `on_processing` – Callback invoked with the [`PaymentIntent`] while it is `processing`.
+/// * `on_next_action` – Callback invoked when a voucher or QR code must still be paid.
+/// * `poll_processing` – Keep polling a `processing` intent until it settles.
+/// * `clear_query_params` – Remove Stripe's params from the address bar once
+///   handled, so a refresh does not re-run the check (default: `true`).
+/// * `children` – Rendered unchanged; the handler has no UI of its own.
+#[derive(Properties, PartialEq, Clone)]
+pub struct StripeReturnHandlerProps {
+    pub publishable_key: String,
+    #[prop_or_default]
+    pub stripe_options: Option<StripeOptions>,
+    #[prop_or_default]
+    pub on_success: Callback<StripeCheckoutSuccess>,
+    #[prop_or_default]
+    pub on_error: Callback<StripeError>,
+    #[prop_or_default]
+    pub on_processing: Callback<PaymentIntent>,
+    #[prop_or_default]
+    pub on_next_action: Callback<NextAction>,
+    #[prop_or_default]
+    pub poll_processing: Option<PollOptions>,
+    #[prop_or(true)]
+    pub clear_query_params: bool,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Yew function component that completes redirect-based payments.
+///
+/// Mount it on the page your `return_url` points to. When the URL carries
+/// Stripe's redirect params it retrieves the PaymentIntent and emits the same
+/// `on_success`/`on_error` callbacks as [`StripeCheckout`](crate::StripeCheckout);
+/// otherwise it does nothing.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// html! {
+///     <StripeReturnHandler
+///         publishable_key="pk_test_123".to_string()
+///         on_success={on_success}
+///         on_error={on_error}
+///     >
+///         <OrderPage />
+///     </StripeReturnHandler>
+/// }
+/// ```
+#[function_component(StripeReturnHandler)]
+pub fn stripe_return_handler(props: &StripeReturnHandlerProps) -> Html {
+    let state = use_stripe_return(
+        props.publishable_key.clone(),
+        props.stripe_options.clone(),
+        props.poll_processing.clone(),
+    );
+
+    {
+        let on_success = props.on_success.clone();
+        let on_error = props.on_error.clone();
+        let on_processing = props.on_processing.clone();
+        let on_next_action = props.on_next_action.clone();
+        let clear = props.clear_query_params;
+        use_effect_with(state, move |state| {
+            let handled = match state {
+                StripeReturnState::Succeeded(s) => {
+                    on_success.emit(s.clone());
+                    true
+                }
+                StripeReturnState::Failed(e) => {
+                    on_error.emit(e.clone());
+                    true
+                }
+                // Keep the params so a refresh checks the payment again.
+                StripeReturnState::Processing(intent) => {
+                    on_processing.emit((**intent).clone());
+                    false
+                }
+                StripeReturnState::RequiresAction(action) => {
+                    on_next_action.emit(action.clone());
+                    false
+                }
+                _ => false,
+            };
+            if handled && clear {
+                clear_return_params();
+            }
+            || ()
+        });
+    }
+
+    html! { <>{ for props.children.iter() }</> }
+}
+
+/// Drop Stripe's redirect params from the address bar without reloading.
+fn clear_return_params() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Ok(href) = window.location().href() else {
+        return;
+    };
+    let Ok(url) = web_sys::Url::new(&href) else {
+        return;
+    };
+    let params = url.search_params();
+    for key in [
+        "payment_intent",
+        "payment_intent_client_secret",
+        "redirect_status",
+    ] {
+        params.delete(key);
+    }
+    if let Ok(history) = window.history() {
+        let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url.href()));
+    }
+}
@@ -0,0 +1,115 @@
+//! Shared plumbing for the button components that run one Stripe.js call per
+//! click: [`StripeMicrodepositForm`](crate::StripeMicrodepositForm),
+//! [`StripeFinancialConnectionsButton`](crate::StripeFinancialConnectionsButton)
+//! and [`StripeIdentityButton`](crate::StripeIdentityButton).
+
+use crate::client::{StripeClient, StripeError, StripeOptions};
+use crate::use_stripejs;
+use std::cell::Cell;
+use std::future::Future;
+use std::rc::Rc;
+use yew::functional::hook;
+use yew::prelude::*;
+
+/// Loading and error state for one click-driven Stripe.js call.
+#[derive(Clone)]
+pub(crate) struct StripeAction {
+    ready: bool,
+    loading: UseStateHandle<bool>,
+    error: UseStateHandle<Option<String>>,
+    alive: Rc<Cell<bool>>,
+    publishable_key: String,
+    stripe_options: Option<StripeOptions>,
+    on_error: Callback<StripeError>,
+}
+
+/// Custom hook: load Stripe.js and track one action's loading and error state.
+#[hook]
+pub(crate) fn use_stripe_action(
+    publishable_key: String,
+    stripe_options: Option<StripeOptions>,
+    on_error: Callback<StripeError>,
+) -> StripeAction {
+    let ready = use_stripejs();
+    let loading = use_state(|| false);
+    let error = use_state(|| None::<String>);
+    let alive = use_mut_ref(|| Rc::new(Cell::new(true))).borrow().clone();
+    {
+        let alive = alive.clone();
+        use_effect_with((), move |_| move || alive.set(false));
+    }
+    StripeAction {
+        ready,
+        loading,
+        error,
+        alive,
+        publishable_key,
+        stripe_options,
+        on_error,
+    }
+}
+
+impl StripeAction {
+    /// Run `action` with a new client unless a run is in progress, passing
+    /// its result to `on_ok`. Failures go to `on_error` and are shown inline.
+    pub(crate) fn run<T, F, Fut>(&self, action: F, on_ok: impl FnOnce(T) + 'static)
+    where
+        F: FnOnce(StripeClient) -> Fut,
+        Fut: Future<Output = Result<T, StripeError>> + 'static,
+    {
+        if *self.loading {
+            return;
+        }
+        let client =
+            match StripeClient::from_options(&self.publishable_key, self.stripe_options.as_ref()) {
+                Ok(client) => client,
+                Err(e) => return self.report(e),
+            };
+        self.loading.set(true);
+        self.error.set(None);
+        let fut = action(client);
+        let this = self.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            match fut.await {
+                Ok(value) => on_ok(value),
+                Err(e) => this.report(e),
+            }
+            if this.alive.get() {
+                this.loading.set(false);
+            }
+        });
+    }
+
+    /// Show `message` inline without running anything, e.g. for invalid input.
+    pub(crate) fn fail(&self, message: impl Into<String>) {
+        self.error.set(Some(message.into()));
+    }
+
+    fn report(&self, e: StripeError) {
+        self.on_error.emit(e.clone());
+        if self.alive.get() {
+            self.error.set(Some(e.message));
+        }
+    }
+
+    /// The action button, disabled until Stripe.js has loaded and while running.
+    pub(crate) fn button(&self, onclick: Callback<MouseEvent>, label: String, busy: &str) -> Html {
+        html! {
+            <button
+                type="button"
+                {onclick}
+                disabled={!self.ready || *self.loading}
+                class="rounded bg-blue-600 text-white font-semibold px-5 py-2 shadow hover:bg-blue-700 transition disabled:opacity-50">
+                { if *self.loading { busy.to_string() } else { label } }
+            </button>
+        }
+    }
+
+    /// The last error, if any.
+    pub(crate) fn error(&self) -> Html {
+        match &*self.error {
+            Some(msg) => html! { <div class="text-red-500 text-sm">{ msg }</div> },
+            None => Html::default(),
+        }
+    }
+}